@@ -0,0 +1,25 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use cryptopals::{kangaroo, DhGroup, KangarooParameters};
+use num_bigint::{BigUint, RandBigInt};
+
+fn bench_kangaroo(c: &mut Criterion) {
+    let group = DhGroup::challenge_58();
+    let mut benchmarks = c.benchmark_group("kangaroo");
+    benchmarks.sample_size(10);
+
+    for bits in [20, 40] {
+        let a = BigUint::ZERO;
+        let b = BigUint::from(1u64 << bits);
+        let parameters = KangarooParameters::for_interval(&b);
+        let x = rand::thread_rng().gen_biguint_below(&b);
+        let y = group.public_key(&x);
+
+        benchmarks.bench_function(format!("interval 2^{bits}"), |bencher| {
+            bencher.iter(|| kangaroo(&group, &y, &a, &b, parameters))
+        });
+    }
+    benchmarks.finish();
+}
+
+criterion_group!(benches, bench_kangaroo);
+criterion_main!(benches);
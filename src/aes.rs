@@ -101,9 +101,17 @@ impl TryFrom<Bytes> for IV {
 pub trait AES: Sized {
     fn aes_ecb(&self, mode: Mode, key: &Key) -> anyhow::Result<Self>;
     fn aes_cbc(&self, mode: Mode, key: &Key, iv: &IV) -> anyhow::Result<Self>;
+
+    /// calculates the CBC-MAC, which is the last block of the CBC encryption
+    fn cbc_mac(&self, key: &Key, iv: &IV) -> anyhow::Result<Self>;
+
+    /// encrypts or decrypts in CTR mode, where the counter block consists of
+    /// the 64 bit nonce and the 64 bit block counter, both little endian
+    fn aes_ctr(&self, key: &Key, nonce: u64) -> anyhow::Result<Self>;
 }
 
 impl AES for Bytes {
+    #[allow(clippy::manual_is_multiple_of)]
     fn aes_ecb(&self, mode: Mode, key: &Key) -> anyhow::Result<Self> {
         if self.len() % AES_BLOCKSIZE != 0 {
             return Err(AESError::UnpaddedData(self.len()).into());
@@ -120,6 +128,7 @@ impl AES for Bytes {
         Ok(Bytes::from(&output[0..count]))
     }
 
+    #[allow(clippy::manual_is_multiple_of)]
     fn aes_cbc(&self, mode: Mode, key: &Key, iv: &IV) -> anyhow::Result<Self> {
         let mut result = Vec::new();
         let mut previous_block = Bytes::from(&iv[..]);
@@ -157,6 +166,24 @@ impl AES for Bytes {
 
         Ok(result.into())
     }
+
+    fn cbc_mac(&self, key: &Key, iv: &IV) -> anyhow::Result<Self> {
+        let encrypted = self.aes_cbc(Mode::Encrypt, key, iv)?;
+        if encrypted.is_empty() {
+            return Err(AESError::UnpaddedData(0).into());
+        }
+        Ok(Bytes::from(&encrypted[encrypted.len() - AES_BLOCKSIZE..]))
+    }
+
+    fn aes_ctr(&self, key: &Key, nonce: u64) -> anyhow::Result<Self> {
+        let mut result = Vec::with_capacity(self.len());
+        for (counter, chunk) in (0u64..).zip(self[..].chunks(AES_BLOCKSIZE)) {
+            let counter_block = Bytes::from([nonce.to_le_bytes(), counter.to_le_bytes()].concat());
+            let keystream = counter_block.aes_ecb(Mode::Encrypt, key)?;
+            result.extend(chunk.iter().zip(&keystream[..]).map(|(lhs, rhs)| lhs ^ rhs));
+        }
+        Ok(result.into())
+    }
 }
 
 #[cfg(test)]
@@ -194,4 +221,21 @@ mod tests {
 
         assert_eq!(test_data, decrypted);
     }
+
+    #[test]
+    fn test_ctr() {
+        // the ciphertext of challenge 18
+        let encrypted = Bytes::from_base64(
+            "L77na/nrFsKvynd6HzOoG7GHTLXsTVu9qvY/2syLXzhPweyyMTJULu/6/kXX0KSvoOLSFQ==",
+        )
+        .unwrap();
+        let key = Bytes::from_ascii("YELLOW SUBMARINE").try_into().unwrap();
+
+        let decrypted = encrypted.aes_ctr(&key, 0).unwrap();
+        assert_eq!(
+            decrypted,
+            Bytes::from_ascii("Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby ")
+        );
+        assert_eq!(decrypted.aes_ctr(&key, 0).unwrap(), encrypted);
+    }
 }
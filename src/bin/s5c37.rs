@@ -0,0 +1,29 @@
+use anyhow::Result;
+use cryptopals::{SrpParameters, SrpServer, ZeroKeyClient};
+
+fn main() -> Result<()> {
+    let mut server = SrpServer::new(SrpParameters::default());
+    server.register("alice@example.com", "tr0ub4dor&3");
+
+    for multiple in 0..3 {
+        let client = ZeroKeyClient::new("alice@example.com", multiple);
+        println!(
+            "login with A = {multiple} * N: {}",
+            if client.login(&mut server)? {
+                "accepted"
+            } else {
+                "rejected"
+            }
+        );
+    }
+
+    let mut server = SrpServer::hardened(SrpParameters::default());
+    server.register("alice@example.com", "tr0ub4dor&3");
+    let client = ZeroKeyClient::new("alice@example.com", 0);
+    match client.login(&mut server) {
+        Ok(_) => println!("hardened server accepted A = 0"),
+        Err(why) => println!("hardened server: {why}"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use std::fs;
+
+use anyhow::{bail, Result};
+use cryptopals::{MitmSimplifiedServer, SimplifiedSrpClient, SrpParameters};
+use rand::seq::SliceRandom;
+
+fn main() -> Result<()> {
+    let wordlist: Vec<String> = fs::read_to_string("data/wordlist.txt")?
+        .lines()
+        .map(String::from)
+        .collect();
+    let password = wordlist.choose(&mut rand::thread_rng()).unwrap();
+
+    let mut server = MitmSimplifiedServer::new(SrpParameters::default());
+    let client = SimplifiedSrpClient::new(SrpParameters::default(), "alice@example.com", password);
+    client.login(&mut server)?;
+
+    match server.crack_password("alice@example.com", &wordlist) {
+        Some(cracked) => {
+            assert_eq!(&cracked, password);
+            println!("cracked password: {cracked}");
+        }
+        None => bail!("password not found in wordlist"),
+    }
+    Ok(())
+}
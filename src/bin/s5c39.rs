@@ -0,0 +1,18 @@
+use anyhow::Result;
+use cryptopals::{Bytes, RsaPrivateKey};
+use encoding_rs::WINDOWS_1252;
+use num_bigint::BigUint;
+
+fn main() -> Result<()> {
+    let key = RsaPrivateKey::generate(1024, 3);
+
+    let m = BigUint::from(42u32);
+    let c = key.public_key().encrypt(&m)?;
+    assert_eq!(key.decrypt(&c)?, m);
+
+    let message = Bytes::from_ascii("a secret message");
+    let encrypted = key.public_key().encrypt_bytes(&message)?;
+    let decrypted = key.decrypt_bytes(&encrypted)?;
+    println!("{}", decrypted.to_string(WINDOWS_1252).0);
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use anyhow::Result;
+use cryptopals::{rsa_broadcast_attack, Bytes, RsaPrivateKey};
+use encoding_rs::WINDOWS_1252;
+
+fn main() -> Result<()> {
+    let message = Bytes::from_ascii("I'm back and I'm ringin' the bell");
+
+    let mut ciphertexts = Vec::new();
+    for _ in 0..3 {
+        let key = RsaPrivateKey::generate(1024, 3).public_key();
+        let c = key.encrypt(&message.to_biguint())?;
+        ciphertexts.push((key, c));
+    }
+
+    let recovered = Bytes::from(rsa_broadcast_attack(&ciphertexts)?);
+    assert_eq!(recovered, message);
+    println!("{}", recovered.to_string(WINDOWS_1252).0);
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use anyhow::Result;
+use cryptopals::{recover_unpadded_message, Bytes, RsaDecryptionServer, RsaPrivateKey};
+use encoding_rs::WINDOWS_1252;
+
+fn main() -> Result<()> {
+    let mut server = RsaDecryptionServer::new(RsaPrivateKey::generate(1024, 65537));
+
+    let message = Bytes::from_ascii("{time: 1356304276, social: '555-55-5555'}");
+    let c = server.public_key().encrypt(&message.to_biguint())?;
+
+    // the victim decrypts its message, so that we cannot replay it
+    server.decrypt(&c)?;
+    assert!(server.decrypt(&c).is_err());
+
+    let recovered = Bytes::from(recover_unpadded_message(&mut server, &c)?);
+    assert_eq!(recovered, message);
+    println!("{}", recovered.to_string(WINDOWS_1252).0);
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anyhow::{bail, Result};
+use cryptopals::{forge_pkcs1_sha1_signature, RsaPrivateKey};
+
+fn main() -> Result<()> {
+    let public_key = RsaPrivateKey::generate(1024, 3).public_key();
+    let message = b"hi mom";
+
+    let Some(forged) = forge_pkcs1_sha1_signature(&public_key, message) else {
+        bail!("unable to forge a signature");
+    };
+
+    println!("forged signature: {forged}");
+    println!(
+        "sloppy verifier: {}",
+        public_key.verify_pkcs1_sha1_sloppy(message, &forged)
+    );
+    println!(
+        "strict verifier: {}",
+        public_key.verify_pkcs1_sha1(message, &forged)
+    );
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anyhow::{bail, Result};
+use cryptopals::{
+    dsa_hash, recover_key_from_bounded_nonce, Bytes, DsaParameters, DsaPublicKey, DsaSignature,
+};
+use num_bigint::BigUint;
+
+fn main() -> Result<()> {
+    let y = BigUint::parse_bytes(
+        b"84ad4719d044495496a3201c8ff484feb45b962e7302e56a392aee4\
+        abab3e4bdebf2955b4736012f21a08084056b19bcd7fee56048e004\
+        e44984e2f411788efdc837a0d2e5abb7b555039fd243ac01f0fb2ed\
+        1dec568280ce678e931868d23eb095fde9d3779191b8c0299d6e07b\
+        bb283e6633451e535c45513b2d33c99ea17",
+        16,
+    )
+    .unwrap();
+    let public_key = DsaPublicKey::new(DsaParameters::default(), y);
+
+    let message = b"For those that envy a MC it can be hazardous to your health\n\
+    So be friendly, a matter of life and death, just like a etch-a-sketch\n";
+    assert_eq!(
+        Bytes::from(dsa_hash(message)).to_hex(),
+        "d2d0714f014a9784047eaeccf956520045c45265"
+    );
+
+    let signature = DsaSignature::new(
+        BigUint::parse_bytes(b"548099063082341131477253921760299949438196259240", 10).unwrap(),
+        BigUint::parse_bytes(b"857042759984254168557880549501802188789837994940", 10).unwrap(),
+    );
+
+    let Some(key) = recover_key_from_bounded_nonce(&public_key, message, &signature, 0..(1 << 16))
+    else {
+        bail!("unable to find the nonce");
+    };
+
+    println!("x = {}", key.x().to_str_radix(16));
+    println!("fingerprint = {}", key.fingerprint());
+    assert_eq!(
+        key.fingerprint().to_hex(),
+        "0954edd5e0afe5542a4adf012611a91912a3ec16"
+    );
+    Ok(())
+}
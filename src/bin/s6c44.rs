@@ -0,0 +1,32 @@
+use std::fs;
+
+use anyhow::{bail, Result};
+use cryptopals::{recover_key_from_repeated_nonce, DsaParameters, DsaPublicKey, SignatureDump};
+use num_bigint::BigUint;
+
+fn main() -> Result<()> {
+    let dump: SignatureDump = fs::read_to_string("data/44.txt")?.parse()?;
+    let y = BigUint::parse_bytes(
+        b"2d026f4bf30195ede3a088da85e398ef869611d0f68f\
+        0713d51c9c1a3a26c95105d915e2d8cdf26d056b86b8a7b8\
+        5519b1c23cc3ecdc6062650462e3063bd179c2a6581519f6\
+        74a61f1d89a1fff27171ebc1b93d4dc57bceb7ae2430f98a\
+        6a4d83d8279ee65d71c1203d2c96d65ebbf7cce9d32971c3\
+        de5084cce04a2e147821",
+        16,
+    )
+    .unwrap();
+    let public_key = DsaPublicKey::new(DsaParameters::default(), y);
+
+    let Some(key) = recover_key_from_repeated_nonce(&public_key, dump.messages()) else {
+        bail!("found no signatures with a repeated nonce");
+    };
+
+    println!("x = {}", key.x().to_str_radix(16));
+    println!("fingerprint = {}", key.fingerprint());
+    assert_eq!(
+        key.fingerprint().to_hex(),
+        "ca8f6f7c66fa362d40760d135b763eb8527d3d52"
+    );
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anyhow::Result;
+use cryptopals::{
+    magic_signature, tampered_parameters, DsaParameters, DsaPrivateKey, DsaPublicKey,
+};
+
+fn main() -> Result<()> {
+    let key = DsaPrivateKey::generate(DsaParameters::default());
+    let public_key = DsaPublicKey::new(
+        tampered_parameters(key.parameters()),
+        key.public_key().y().clone(),
+    );
+
+    let signature = magic_signature(&public_key);
+    println!("r = {}", signature.r());
+    println!("s = {}", signature.s());
+
+    for message in ["Hello, world", "Goodbye, world"] {
+        println!(
+            "{message:?}: verify = {}, verify_strict = {}",
+            public_key.verify(message.as_bytes(), &signature),
+            public_key.verify_strict(message.as_bytes(), &signature)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anyhow::Result;
+use cryptopals::{rsa_parity_attack, Bytes, RsaParityOracle, RsaPrivateKey};
+use encoding_rs::WINDOWS_1252;
+
+fn main() -> Result<()> {
+    let mut oracle = RsaParityOracle::new(RsaPrivateKey::generate(1024, 65537));
+    let message = Bytes::from_base64(
+        "VGhhdCdzIHdoeSBJIGZvdW5kIHlvdSBkb24ndCBwbGF5IGFyb3VuZCB3aXRoIHRoZSBGdW5reSBDb2xkIE1lZGluYQ==",
+    )?;
+    let c = oracle.public_key().encrypt(&message.to_biguint())?;
+
+    let recovered = rsa_parity_attack(&mut oracle, &c, |upper_bound| {
+        let partial = Bytes::from(upper_bound);
+        let text: String = partial
+            .to_string(WINDOWS_1252)
+            .0
+            .chars()
+            .map(|c| {
+                if c.is_ascii_graphic() || c == ' ' {
+                    c
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        println!("{text}");
+    })?;
+
+    assert_eq!(Bytes::from(recovered), message);
+    println!("needed {} queries", oracle.queries());
+    Ok(())
+}
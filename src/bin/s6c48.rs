@@ -0,0 +1,15 @@
+use anyhow::Result;
+use cryptopals::{bleichenbacher_attack, Bytes, Pkcs1PaddingOracle, RsaPrivateKey};
+use encoding_rs::WINDOWS_1252;
+
+fn main() -> Result<()> {
+    let mut oracle = Pkcs1PaddingOracle::new(RsaPrivateKey::generate(768, 3));
+    let message = Bytes::from_ascii("kick it, CC");
+    let c = oracle.public_key().encrypt_pkcs1(&message)?.to_biguint();
+
+    let padded = Bytes::from(bleichenbacher_attack(&mut oracle, &c)?);
+    println!("recovered: {padded}");
+    println!("{}", padded.to_string(WINDOWS_1252).0);
+    println!("needed {} queries", oracle.queries());
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anyhow::{bail, Result};
+use cryptopals::{forge_transfer, forge_transfer_list, Bytes, Key, TransferClient, TransferServer};
+use encoding_rs::WINDOWS_1252;
+
+fn main() -> Result<()> {
+    let key = Key::random_128();
+    let server = TransferServer::new(key);
+    let victim = TransferClient::new(key, Bytes::from_ascii("42"));
+    let attacker = TransferClient::new(key, Bytes::from_ascii("66"));
+
+    // the API with an attacker controlled IV
+    let Some(forged) = forge_transfer(&attacker, victim.account(), 1_000_000)? else {
+        bail!("unable to forge a transfer");
+    };
+    let transfer = server.process_transfer(&forged)?;
+    println!(
+        "transfer {} from {} to {}",
+        transfer.amount(),
+        transfer.from().to_string(WINDOWS_1252).0,
+        transfer.to().to_string(WINDOWS_1252).0
+    );
+
+    // the API with a fixed IV; we need to capture a request of the victim
+    for amount in 1.. {
+        let captured = victim.sign_transfer_list(&[(Bytes::from_ascii("7"), amount)])?;
+        if let Some(forged) = forge_transfer_list(&captured, &attacker, 1_000_000)? {
+            let list = server.process_transfer_list(&forged)?;
+            for (to, amount) in list.transactions() {
+                println!(
+                    "transfer {amount} from {} to {:?}",
+                    list.from().to_string(WINDOWS_1252).0,
+                    to
+                );
+            }
+            break;
+        }
+    }
+    Ok(())
+}
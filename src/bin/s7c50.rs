@@ -0,0 +1,19 @@
+use anyhow::{bail, Result};
+use cryptopals::{cbc_mac_hash, forge_javascript, Bytes};
+use encoding_rs::WINDOWS_1252;
+
+fn main() -> Result<()> {
+    let original = Bytes::from_ascii("alert('MZA who was that?');\n");
+    assert_eq!(
+        cbc_mac_hash(&original).to_hex(),
+        "296b8d7cb78a243dda4d0a61d33bbdd1"
+    );
+
+    let Some(forged) = forge_javascript("alert('Ayo, the Wu is back!');", &original) else {
+        bail!("unable to forge a snippet");
+    };
+
+    println!("{:?}", forged.to_string(WINDOWS_1252).0);
+    println!("hash: {}", cbc_mac_hash(&forged));
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use cryptopals::{recover_session_id, CompressionOracle, OracleCipher};
+
+const SESSION_ID: &str = "TmV2ZXIgcmV2ZWFsIHRoZSBXdS1UYW5nIFNlY3JldCE=";
+
+fn main() {
+    for cipher in [OracleCipher::Ctr, OracleCipher::Cbc] {
+        let oracle = CompressionOracle::new(cipher, SESSION_ID);
+        let recovered = recover_session_id(&oracle, 64);
+
+        assert_eq!(recovered, SESSION_ID);
+        println!("{cipher:?}: sessionid={recovered}");
+        println!("needed {} queries", oracle.queries());
+    }
+}
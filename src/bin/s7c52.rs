@@ -0,0 +1,20 @@
+use cryptopals::{cascade_collision, MdHash};
+
+fn main() {
+    let cheap = MdHash::new(16);
+    let expensive = MdHash::new(24);
+
+    let (lhs, rhs) = cascade_collision(&cheap, &expensive);
+    assert_ne!(lhs, rhs);
+    assert_eq!(cheap.hash(&lhs), cheap.hash(&rhs));
+    assert_eq!(expensive.hash(&lhs), expensive.hash(&rhs));
+
+    println!("{lhs}");
+    println!("{rhs}");
+    println!("f || g = {}{}", cheap.hash(&lhs), expensive.hash(&lhs));
+    println!(
+        "needed {} calls of f and {} calls of g",
+        cheap.compressions(),
+        expensive.compressions()
+    );
+}
@@ -0,0 +1,33 @@
+use cryptopals::{second_preimage, Bytes, MdHash, AES_BLOCKSIZE};
+
+fn main() {
+    let hash = MdHash::new(32);
+    let k = 16;
+    let message = Bytes::from(
+        (0..AES_BLOCKSIZE << k)
+            .map(|_| rand::random::<u8>())
+            .collect::<Vec<_>>(),
+    );
+    let digest = hash.hash(&message);
+    let before = hash.compressions();
+
+    let forged = second_preimage(&hash, &message).expect("the message is long enough");
+    let needed = hash.compressions() - before;
+    assert_ne!(forged, message);
+    assert_eq!(forged.len(), message.len());
+    assert_eq!(hash.hash(&forged), digest);
+
+    let differing = (0..message.len())
+        .filter(|i| message[*i] != forged[*i])
+        .count();
+    println!("hash of both messages: {digest}");
+    println!(
+        "the messages differ in {differing} of {} bytes",
+        message.len()
+    );
+    println!(
+        "needed {} compressions for a {} bit state",
+        needed,
+        8 * hash.state_size()
+    );
+}
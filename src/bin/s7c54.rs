@@ -0,0 +1,33 @@
+use cryptopals::{Bytes, DiamondStructure, MdHash, AES_BLOCKSIZE};
+
+const PREFIX_BLOCKS: usize = 8;
+
+fn main() {
+    let hash = MdHash::new(24);
+    let diamond = DiamondStructure::build(&hash, 8);
+    let commitment = diamond.commitment(&hash, PREFIX_BLOCKS);
+    println!("I predict the results of this season, their hash is {commitment}");
+    println!(
+        "building the diamond structure needed {} compressions",
+        hash.compressions()
+    );
+
+    let results = "Final results: Tigers 3:1 Lions, Bears 0:2 Wolves, \
+        Eagles 1:1 Hawks, Sharks 4:0 Dolphins";
+    let prefix = Bytes::from_ascii(&format!(
+        "{results:width$}",
+        width = PREFIX_BLOCKS * AES_BLOCKSIZE
+    ));
+    assert_eq!(prefix.len(), PREFIX_BLOCKS * AES_BLOCKSIZE);
+
+    let before = hash.compressions();
+    let message = diamond
+        .herd(&hash, &prefix)
+        .expect("the prefix consists of whole blocks");
+    let needed = hash.compressions() - before;
+    assert_eq!(hash.hash(&message), commitment);
+
+    println!("{results}");
+    println!("{message}");
+    println!("herding needed {needed} compressions");
+}
@@ -0,0 +1,26 @@
+use cryptopals::{find_md4_collision, md4};
+
+const COLLISIONS: usize = 10;
+
+fn main() {
+    let mut total_attempts = 0;
+    for _ in 0..COLLISIONS {
+        let collision = find_md4_collision();
+        assert_ne!(collision.lhs(), collision.rhs());
+        assert_eq!(md4(&collision.lhs()[..]), md4(&collision.rhs()[..]));
+
+        println!("{}", collision.lhs());
+        println!("{}", collision.rhs());
+        println!(
+            "MD4 = {} after {} attempts\n",
+            md4(&collision.lhs()[..]),
+            collision.attempts()
+        );
+        total_attempts += collision.attempts();
+    }
+
+    println!(
+        "found {COLLISIONS} collisions in {total_attempts} attempts, a success rate of {:.4}%",
+        100.0 * COLLISIONS as f64 / total_attempts as f64
+    );
+}
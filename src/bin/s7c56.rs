@@ -0,0 +1,29 @@
+use anyhow::Result;
+use cryptopals::{recover_rc4_cookie, Bytes, Rc4CookieOracle};
+use encoding_rs::WINDOWS_1252;
+
+/// the default number of samples per prefix length; it can be overridden
+/// with the first argument
+const SAMPLES: usize = 1 << 24;
+
+fn main() -> Result<()> {
+    let samples = match std::env::args().nth(1) {
+        Some(samples) => samples.parse()?,
+        None => SAMPLES,
+    };
+
+    let cookie = Bytes::from_base64("QkUgU1VSRSBUTyBEUklOSyBZT1VSIE9WQUxUSU5F")?;
+    let oracle = Rc4CookieOracle::new(cookie.clone());
+
+    let recovered = recover_rc4_cookie(&oracle, samples);
+    let correct = (0..cookie.len())
+        .filter(|i| cookie[*i] == recovered[*i])
+        .count();
+
+    println!("{:?}", recovered.to_string(WINDOWS_1252).0);
+    println!(
+        "recovered {correct} of {} bytes with {samples} samples per prefix",
+        cookie.len()
+    );
+    Ok(())
+}
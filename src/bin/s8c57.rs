@@ -0,0 +1,14 @@
+use anyhow::{bail, Result};
+use cryptopals::{recover_key_by_subgroup_confinement, DhGroup, DhMacBot};
+
+fn main() -> Result<()> {
+    let bot = DhMacBot::new(DhGroup::challenge_57());
+
+    let Some(x) = recover_key_by_subgroup_confinement(&bot) else {
+        bail!("the small subgroups do not cover the private key");
+    };
+    assert_eq!(bot.group().public_key(&x), bot.public_key());
+
+    println!("recovered the private key of the bot: {x}");
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anyhow::{bail, Result};
+use cryptopals::{kangaroo, recover_key_with_kangaroo, DhGroup, DhMacBot, KangarooParameters};
+use num_bigint::{BigUint, RandBigInt};
+
+fn main() -> Result<()> {
+    let group = DhGroup::challenge_58();
+
+    for bits in [20, 40] {
+        let (a, b) = (BigUint::ZERO, BigUint::from(1u64 << bits));
+        let x = rand::thread_rng().gen_biguint_below(&b);
+        let parameters = KangarooParameters::for_interval(&b);
+
+        match kangaroo(&group, &group.public_key(&x), &a, &b, parameters) {
+            Some(index) => {
+                assert_eq!(index, x);
+                println!("found the discrete log {index} in [0, 2^{bits}]");
+            }
+            None => println!("the kangaroos missed each other in [0, 2^{bits}]"),
+        }
+    }
+
+    let bot = DhMacBot::new(group);
+    let Some(x) = recover_key_with_kangaroo(&bot) else {
+        bail!("unable to recover the private key");
+    };
+    assert_eq!(bot.group().public_key(&x), bot.public_key());
+    println!("recovered the private key of the bot: {x}");
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use anyhow::{bail, Result};
+use cryptopals::{recover_key_by_invalid_curves, EcGroup, EcdhMacBot};
+
+fn main() -> Result<()> {
+    let bot = EcdhMacBot::new(EcGroup::challenge_59());
+
+    let Some(d) = recover_key_by_invalid_curves(&bot) else {
+        bail!("the invalid curves do not cover the private key");
+    };
+    assert_eq!(bot.group().public_key(&d), bot.public_key());
+
+    println!("recovered the private key of the bot: {d}");
+    Ok(())
+}
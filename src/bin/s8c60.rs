@@ -0,0 +1,14 @@
+use anyhow::{bail, Result};
+use cryptopals::{recover_key_by_twist_attack, MontgomeryGroup, MontgomeryMacBot};
+
+fn main() -> Result<()> {
+    let bot = MontgomeryMacBot::new(MontgomeryGroup::challenge_60());
+
+    let Some(d) = recover_key_by_twist_attack(&bot) else {
+        bail!("the kangaroos did not find the private key");
+    };
+    assert_eq!(bot.group().public_key(&d), bot.public_key());
+
+    println!("recovered the private key of the bot: {d}");
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anyhow::{bail, Result};
+use cryptopals::{forge_ecdsa_key, forge_rsa_key, EcGroup, EcdsaPrivateKey, RsaPrivateKey};
+
+const MESSAGE: &[u8] = b"hi mom";
+
+fn main() -> Result<()> {
+    let key = EcdsaPrivateKey::generate(EcGroup::challenge_59());
+    let signature = key.sign(MESSAGE);
+    let Some(forged) = forge_ecdsa_key(&key.public_key(), MESSAGE, &signature) else {
+        bail!("the ECDSA signature is not valid");
+    };
+    assert!(forged.public_key().verify(MESSAGE, &signature));
+    println!(
+        "ECDSA: the signature also verifies under the key {:?}",
+        forged.public_key().point()
+    );
+
+    let key = RsaPrivateKey::generate(1024, 3);
+    let Some(signature) = key.sign_pkcs1_sha1(MESSAGE) else {
+        bail!("the RSA key is too short to sign");
+    };
+    let Some(forged) = forge_rsa_key(&key.public_key(), MESSAGE, &signature) else {
+        bail!("the RSA signature is not valid");
+    };
+    assert!(forged.public_key().verify_pkcs1_sha1(MESSAGE, &signature));
+    println!(
+        "RSA: the signature also verifies under the key\n{}",
+        forged.public_key()
+    );
+    Ok(())
+}
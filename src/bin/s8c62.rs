@@ -0,0 +1,25 @@
+use anyhow::{bail, Result};
+use cryptopals::{recover_key_from_biased_nonces, BiasedNonceSigner, Bytes, EcGroup};
+
+fn main() -> Result<()> {
+    let count: usize = std::env::args().nth(1).map_or(Ok(22), |arg| arg.parse())?;
+    let signer = BiasedNonceSigner::new(EcGroup::challenge_59());
+    let messages: Vec<_> = (0..count)
+        .map(|i| {
+            let message = Bytes::from_ascii(&format!("message number {i}"));
+            let signature = signer.sign(&message[..]);
+            (message, signature)
+        })
+        .collect();
+
+    let Some(key) = recover_key_from_biased_nonces(&signer.public_key(), &messages) else {
+        bail!("the lattice did not reveal the private key");
+    };
+    assert_eq!(key.public_key(), signer.public_key());
+
+    println!(
+        "recovered the private key from {count} signatures: {}",
+        key.d()
+    );
+    Ok(())
+}
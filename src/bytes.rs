@@ -9,6 +9,7 @@ use std::{
 
 use base64ct::Base64;
 use encoding_rs::{Encoding, WINDOWS_1252};
+use num_bigint::BigUint;
 
 use crate::{PadWith, PaddingError, PaddingScheme, Unpad};
 
@@ -52,6 +53,18 @@ impl From<u8> for Bytes {
     }
 }
 
+impl From<&BigUint> for Bytes {
+    fn from(value: &BigUint) -> Self {
+        Self(value.to_bytes_be())
+    }
+}
+
+impl From<BigUint> for Bytes {
+    fn from(value: BigUint) -> Self {
+        Self::from(&value)
+    }
+}
+
 impl Display for Bytes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.to_hex())
@@ -263,6 +276,19 @@ impl Bytes {
         self.0
     }
 
+    /// interprets the bytes as big endian unsigned integer
+    ///
+    /// ```rust
+    /// use cryptopals::Bytes;
+    /// use num_bigint::BigUint;
+    /// let b = Bytes::from(vec![0x01, 0x00]);
+    /// assert_eq!(b.to_biguint(), BigUint::from(256u32));
+    /// assert_eq!(Bytes::from(b.to_biguint()), b);
+    /// ```
+    pub fn to_biguint(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.0)
+    }
+
     pub fn append(&mut self, b: u8) {
         self.0.push(b)
     }
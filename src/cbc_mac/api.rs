@@ -0,0 +1,89 @@
+use rand::random;
+
+use crate::{Bytes, Key, Transfer, TransferError, TransferList, AES, AES_BLOCKSIZE, IV};
+
+/// the bank's API server, which shares a key with all clients and processes
+/// requests which carry a valid CBC-MAC
+pub struct TransferServer {
+    key: Key,
+}
+
+/// the client of an account holder, which only signs transfers from its own
+/// account
+pub struct TransferClient {
+    key: Key,
+    account: Bytes,
+}
+
+fn split_at_end(request: &Bytes, length: usize) -> Result<(Bytes, Bytes), TransferError> {
+    if request.len() < length {
+        return Err(TransferError::RequestTooShort);
+    }
+    let idx = request.len() - length;
+    Ok((Bytes::from(&request[..idx]), Bytes::from(&request[idx..])))
+}
+
+fn mac(message: &Bytes, key: &Key, iv: &IV) -> Bytes {
+    message
+        .cbc_mac(key, iv)
+        .expect("the CBC-MAC of a nonempty message can always be calculated")
+}
+
+impl TransferServer {
+    pub fn new(key: Key) -> Self {
+        Self { key }
+    }
+
+    /// processes a request of the form `message || IV || MAC`
+    pub fn process_transfer(&self, request: &Bytes) -> Result<Transfer, TransferError> {
+        let (rest, tag) = split_at_end(request, AES_BLOCKSIZE)?;
+        let (message, iv) = split_at_end(&rest, AES_BLOCKSIZE)?;
+        let iv: IV = iv.try_into().unwrap();
+
+        if message.is_empty() || mac(&message, &self.key, &iv) != tag {
+            return Err(TransferError::InvalidMac);
+        }
+        Transfer::parse(&message)
+    }
+
+    /// processes a request of the form `message || MAC`, where the MAC uses a
+    /// zero IV
+    pub fn process_transfer_list(&self, request: &Bytes) -> Result<TransferList, TransferError> {
+        let (message, tag) = split_at_end(request, AES_BLOCKSIZE)?;
+
+        if message.is_empty() || mac(&message, &self.key, &[0; AES_BLOCKSIZE]) != tag {
+            return Err(TransferError::InvalidMac);
+        }
+        TransferList::parse(&message)
+    }
+}
+
+impl TransferClient {
+    pub fn new(key: Key, account: Bytes) -> Self {
+        Self { key, account }
+    }
+
+    pub fn account(&self) -> &Bytes {
+        &self.account
+    }
+
+    /// creates a request `message || IV || MAC` with a random IV
+    pub fn sign_transfer(&self, to: &Bytes, amount: u64) -> Result<Bytes, TransferError> {
+        let transfer = Transfer::new(self.account.clone(), to.clone(), amount)?;
+        let message = transfer.to_bytes();
+        let iv: IV = random();
+        let tag = mac(&message, &self.key, &iv);
+        Ok(message + Bytes::from(&iv[..]) + tag)
+    }
+
+    /// creates a request `message || MAC`, where the MAC uses a zero IV
+    pub fn sign_transfer_list(
+        &self,
+        transactions: &[(Bytes, u64)],
+    ) -> Result<Bytes, TransferError> {
+        let list = TransferList::new(self.account.clone(), Vec::from(transactions))?;
+        let message = list.to_bytes();
+        let tag = mac(&message, &self.key, &[0; AES_BLOCKSIZE]);
+        Ok(message + tag)
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{Bytes, PadWith, Pkcs7, TransferClient, TransferError, TransferList, AES_BLOCKSIZE};
+
+/// the start of every request, which is followed by the account id of the sender
+const FROM_PREFIX: &str = "from=";
+
+/// forges a request which transfers `amount` from `victim` to the account of
+/// `attacker`, if the API lets the client choose the IV (challenge 49)
+///
+/// The attacker signs a transfer from its own account, and replaces the
+/// sender in the first block. Because the first block is XORed with the IV
+/// before it is encrypted, adjusting the IV by the same difference keeps the
+/// MAC valid. This only works if both account ids have the same length and
+/// fit into the first block.
+pub fn forge_transfer(
+    attacker: &TransferClient,
+    victim: &Bytes,
+    amount: u64,
+) -> Result<Option<Bytes>, TransferError> {
+    let own_account = attacker.account();
+    if victim.len() != own_account.len() || FROM_PREFIX.len() + victim.len() > AES_BLOCKSIZE {
+        return Ok(None);
+    }
+
+    let request = attacker.sign_transfer(own_account, amount)?;
+    let message_length = request.len() - 2 * AES_BLOCKSIZE;
+    let message = Bytes::from(&request[..message_length]);
+    let iv = Bytes::from(&request[message_length..message_length + AES_BLOCKSIZE]);
+    let tag = Bytes::from(&request[message_length + AES_BLOCKSIZE..]);
+
+    let forged_message = Bytes::from_ascii(FROM_PREFIX)
+        + victim.clone()
+        + Bytes::from(&message[FROM_PREFIX.len() + victim.len()..]);
+    let difference =
+        Bytes::from(&message[..AES_BLOCKSIZE]) ^ Bytes::from(&forged_message[..AES_BLOCKSIZE]);
+
+    Ok(Some(forged_message + (iv ^ difference) + tag))
+}
+
+/// forges a request with a fixed IV, which contains all transactions of the
+/// captured `victim_request` and additionally transfers `amount` to the
+/// account of `attacker` (challenge 49)
+///
+/// Because the IV is fixed, the MAC of the victim's message can be used to
+/// glue another message to it: `M1 || padding || (M2[..16] ^ MAC(M1)) || M2[16..]`
+/// has the same MAC as `M2`. The first block of `M2` becomes garbage, and
+/// `M2` is constructed so that the garbage ends up behind the amount of the
+/// last transaction of the victim, where it is ignored.
+///
+/// Returns `None` if the account id of the attacker is too long, or if the
+/// garbage block contains separator characters; in this case, the attacker
+/// should simply wait for the next request of the victim.
+pub fn forge_transfer_list(
+    victim_request: &Bytes,
+    attacker: &TransferClient,
+    amount: u64,
+) -> Result<Option<Bytes>, TransferError> {
+    let own_account = attacker.account();
+    if "from=&tx_list=".len() + own_account.len() > AES_BLOCKSIZE {
+        return Ok(None);
+    }
+    if victim_request.len() < AES_BLOCKSIZE {
+        return Err(TransferError::RequestTooShort);
+    }
+
+    let message_length = victim_request.len() - AES_BLOCKSIZE;
+    let mut glued = Bytes::from(&victim_request[..message_length]);
+    let victim_tag = Bytes::from(&victim_request[message_length..]);
+
+    // aes_cbc pads the last block only if it is incomplete
+    if !glued.len().is_multiple_of(AES_BLOCKSIZE) {
+        glued
+            .pad_with(AES_BLOCKSIZE, Pkcs7)
+            .expect("the AES block size is supported by PKCS#7");
+    }
+
+    let own_request =
+        attacker.sign_transfer_list(&[(own_account.clone(), 0), (own_account.clone(), amount)])?;
+    let own_message = &own_request[..own_request.len() - AES_BLOCKSIZE];
+    let own_tag = Bytes::from(&own_request[own_request.len() - AES_BLOCKSIZE..]);
+
+    let forged_message = glued
+        + (Bytes::from(&own_message[..AES_BLOCKSIZE]) ^ victim_tag)
+        + Bytes::from(&own_message[AES_BLOCKSIZE..]);
+
+    let expected_transaction = (own_account.clone(), amount);
+    match TransferList::parse(&forged_message) {
+        Ok(list) if list.transactions().last() == Some(&expected_transaction) => {
+            Ok(Some(forged_message + own_tag))
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{forge_transfer, forge_transfer_list, Bytes, Key, TransferClient, TransferServer};
+
+    #[test]
+    fn test_forge_transfer() {
+        let key = Key::random_128();
+        let server = TransferServer::new(key);
+        let attacker = TransferClient::new(key, Bytes::from_ascii("666"));
+        let victim = Bytes::from_ascii("123");
+
+        let forged = forge_transfer(&attacker, &victim, 1_000_000)
+            .unwrap()
+            .unwrap();
+        let transfer = server.process_transfer(&forged).unwrap();
+        assert_eq!(transfer.from(), &victim);
+        assert_eq!(transfer.to(), attacker.account());
+        assert_eq!(*transfer.amount(), 1_000_000);
+    }
+
+    #[test]
+    fn test_forge_transfer_list() {
+        let key = Key::random_128();
+        let server = TransferServer::new(key);
+        let victim = TransferClient::new(key, Bytes::from_ascii("123"));
+        let attacker = TransferClient::new(key, Bytes::from_ascii("66"));
+
+        let mut forged = None;
+        for amount in 1..100 {
+            let captured = victim
+                .sign_transfer_list(&[
+                    (Bytes::from_ascii("7"), 10),
+                    (Bytes::from_ascii("8"), amount),
+                ])
+                .unwrap();
+            assert!(server.process_transfer_list(&captured).is_ok());
+
+            forged = forge_transfer_list(&captured, &attacker, 1_000_000).unwrap();
+            if forged.is_some() {
+                break;
+            }
+        }
+
+        let list = server.process_transfer_list(&forged.unwrap()).unwrap();
+        assert_eq!(list.from(), victim.account());
+        assert_eq!(
+            list.transactions().last().unwrap(),
+            &(attacker.account().clone(), 1_000_000)
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use lazy_static::lazy_static;
+
+use crate::{Bytes, Key, PadWith, Pkcs7, AES, AES_BLOCKSIZE};
+
+lazy_static! {
+    static ref HASH_KEY: Key = Bytes::from_ascii("YELLOW SUBMARINE").try_into().unwrap();
+}
+
+/// uses CBC-MAC with the key `YELLOW SUBMARINE` and a zero IV as hash
+/// function (challenge 50)
+///
+/// ```rust
+/// use cryptopals::{cbc_mac_hash, Bytes};
+/// let hash = cbc_mac_hash(&Bytes::from_ascii("alert('MZA who was that?');\n"));
+/// assert_eq!(hash.to_hex(), "296b8d7cb78a243dda4d0a61d33bbdd1");
+/// ```
+pub fn cbc_mac_hash(message: &Bytes) -> Bytes {
+    message
+        .clone()
+        .padded_with(AES_BLOCKSIZE, Pkcs7)
+        .expect("the AES block size is supported by PKCS#7")
+        .cbc_mac(&HASH_KEY, &[0; AES_BLOCKSIZE])
+        .expect("a padded message can always be encrypted")
+}
+
+/// creates a JavaScript snippet which starts with `payload` and has the same
+/// CBC-MAC hash as `original`
+///
+/// The payload is followed by a line comment and filled up to the block
+/// boundary. The next block is chosen so that the CBC state equals the state
+/// after the first block of `original`, and the remaining blocks of `original`
+/// are appended unchanged; they all end up in the comment. The filler is
+/// varied until the glue block does not contain a line break, which would
+/// terminate the comment.
+pub fn forge_javascript(payload: &str, original: &Bytes) -> Option<Bytes> {
+    if original.len() < AES_BLOCKSIZE {
+        return None;
+    }
+    let first_block = Bytes::from(&original[..AES_BLOCKSIZE]);
+    let remainder = Bytes::from(&original[AES_BLOCKSIZE..]);
+
+    for counter in 0u32.. {
+        let mut prefix = format!("{payload}//{counter}");
+        while !prefix.len().is_multiple_of(AES_BLOCKSIZE) {
+            prefix.push(' ');
+        }
+        let prefix = Bytes::from_ascii(&prefix);
+
+        // the prefix is already aligned, so aes_cbc will not pad it
+        let state = prefix.cbc_mac(&HASH_KEY, &[0; AES_BLOCKSIZE]).ok()?;
+        let glue = state ^ first_block.clone();
+
+        if !glue[..].iter().any(|b| *b == b'\n' || *b == b'\r') {
+            return Some(prefix + glue + remainder);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cbc_mac_hash, forge_javascript, Bytes};
+
+    #[test]
+    fn test_forge_javascript() {
+        let original = Bytes::from_ascii("alert('MZA who was that?');\n");
+        let payload = "alert('Ayo, the Wu is back!');";
+
+        let forged = forge_javascript(payload, &original).unwrap();
+        assert!(forged[..].starts_with(payload.as_bytes()));
+        assert_eq!(cbc_mac_hash(&forged), cbc_mac_hash(&original));
+        assert_eq!(
+            forged[..].iter().filter(|b| **b == b'\n').count(),
+            1,
+            "the comment must not be terminated early"
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use thiserror::Error;
+
+mod transfer;
+pub use transfer::*;
+
+mod api;
+pub use api::*;
+
+mod forgery;
+pub use forgery::*;
+
+mod hash;
+pub use hash::*;
+
+#[derive(Error, Debug)]
+pub enum TransferError {
+    #[error("the request is too short")]
+    RequestTooShort,
+
+    #[error("the MAC of the request is invalid")]
+    InvalidMac,
+
+    #[error("expected field '{0}'")]
+    MissingField(&'static str),
+
+    #[error("unexpected data after the last field")]
+    TrailingData,
+
+    #[error("invalid account id")]
+    InvalidAccount,
+
+    #[error("invalid amount")]
+    InvalidAmount,
+}
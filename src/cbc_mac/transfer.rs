@@ -0,0 +1,186 @@
+use getset::Getters;
+
+use crate::{Bytes, TransferError};
+
+/// a single transfer, which is encoded as `from=#{from}&to=#{to}&amount=#{amount}`
+/// (the first variant of challenge 49)
+#[derive(Getters, Clone, Debug, Eq, PartialEq)]
+#[getset(get = "pub")]
+pub struct Transfer {
+    from: Bytes,
+    to: Bytes,
+    amount: u64,
+}
+
+/// a list of transfers from a single account, which is encoded as
+/// `from=#{from}&tx_list=#{to:amount(;to:amount)*}` (the second variant of
+/// challenge 49)
+#[derive(Getters, Clone, Debug, Eq, PartialEq)]
+#[getset(get = "pub")]
+pub struct TransferList {
+    from: Bytes,
+    transactions: Vec<(Bytes, u64)>,
+}
+
+/// splits `key=value&...` at the next `&` and checks that the key matches
+fn next_field<'m>(
+    message: &'m [u8],
+    key: &'static str,
+) -> Result<(&'m [u8], &'m [u8]), TransferError> {
+    let rest = message
+        .strip_prefix(key.as_bytes())
+        .and_then(|rest| rest.strip_prefix(b"="))
+        .ok_or(TransferError::MissingField(key))?;
+    match rest.iter().position(|b| *b == b'&') {
+        Some(idx) => Ok((&rest[..idx], &rest[idx + 1..])),
+        None => Ok((rest, &[])),
+    }
+}
+
+/// account ids are opaque to the bank, but must not be empty and must not
+/// contain any of the separators
+fn account(value: &[u8]) -> Result<Bytes, TransferError> {
+    if value.is_empty() || value.iter().any(|b| b"&=;:".contains(b)) {
+        Err(TransferError::InvalidAccount)
+    } else {
+        Ok(Bytes::from(value))
+    }
+}
+
+/// like Ruby's `String#to_i`, the amount consists of the leading digits of
+/// the value; everything after them is ignored
+fn amount(value: &[u8]) -> Result<u64, TransferError> {
+    let digits = value.iter().take_while(|b| b.is_ascii_digit()).count();
+    if digits == 0 {
+        return Err(TransferError::InvalidAmount);
+    }
+    std::str::from_utf8(&value[..digits])
+        .unwrap()
+        .parse()
+        .map_err(|_| TransferError::InvalidAmount)
+}
+
+impl Transfer {
+    pub fn new(from: Bytes, to: Bytes, amount: u64) -> Result<Self, TransferError> {
+        Ok(Self {
+            from: account(&from[..])?,
+            to: account(&to[..])?,
+            amount,
+        })
+    }
+
+    pub fn parse(message: &Bytes) -> Result<Self, TransferError> {
+        let (from, rest) = next_field(&message[..], "from")?;
+        let (to, rest) = next_field(rest, "to")?;
+        let (value, rest) = next_field(rest, "amount")?;
+        if !rest.is_empty() {
+            return Err(TransferError::TrailingData);
+        }
+        Self::new(Bytes::from(from), Bytes::from(to), amount(value)?)
+    }
+
+    pub fn to_bytes(&self) -> Bytes {
+        Bytes::from_ascii("from=")
+            + self.from.clone()
+            + Bytes::from_ascii("&to=")
+            + self.to.clone()
+            + Bytes::from_ascii(&format!("&amount={}", self.amount))
+    }
+}
+
+impl TransferList {
+    pub fn new(from: Bytes, transactions: Vec<(Bytes, u64)>) -> Result<Self, TransferError> {
+        for (to, _) in &transactions {
+            account(&to[..])?;
+        }
+        Ok(Self {
+            from: account(&from[..])?,
+            transactions,
+        })
+    }
+
+    pub fn parse(message: &Bytes) -> Result<Self, TransferError> {
+        let (from, rest) = next_field(&message[..], "from")?;
+        let (tx_list, rest) = next_field(rest, "tx_list")?;
+        if !rest.is_empty() {
+            return Err(TransferError::TrailingData);
+        }
+
+        let transactions = tx_list
+            .split(|b| *b == b';')
+            .map(|transaction| {
+                let idx = transaction
+                    .iter()
+                    .position(|b| *b == b':')
+                    .ok_or(TransferError::MissingField("amount"))?;
+                Ok((
+                    Bytes::from(&transaction[..idx]),
+                    amount(&transaction[idx + 1..])?,
+                ))
+            })
+            .collect::<Result<_, TransferError>>()?;
+
+        Self::new(Bytes::from(from), transactions)
+    }
+
+    pub fn to_bytes(&self) -> Bytes {
+        let mut result =
+            Bytes::from_ascii("from=") + self.from.clone() + Bytes::from_ascii("&tx_list=");
+        for (idx, (to, amount)) in self.transactions.iter().enumerate() {
+            if idx > 0 {
+                result.append(b';');
+            }
+            result = result + to.clone() + Bytes::from_ascii(&format!(":{amount}"));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bytes, Transfer, TransferError, TransferList};
+
+    #[test]
+    fn test_transfer_roundtrip() {
+        let message = Bytes::from_ascii("from=1&to=2&amount=100");
+        let transfer = Transfer::parse(&message).unwrap();
+        assert_eq!(transfer.from(), &Bytes::from_ascii("1"));
+        assert_eq!(transfer.to(), &Bytes::from_ascii("2"));
+        assert_eq!(*transfer.amount(), 100);
+        assert_eq!(transfer.to_bytes(), message);
+    }
+
+    #[test]
+    fn test_transfer_list_roundtrip() {
+        let message = Bytes::from_ascii("from=1&tx_list=2:100;3:5");
+        let list = TransferList::parse(&message).unwrap();
+        assert_eq!(list.transactions().len(), 2);
+        assert_eq!(list.transactions()[1], (Bytes::from_ascii("3"), 5));
+        assert_eq!(list.to_bytes(), message);
+    }
+
+    #[test]
+    fn test_invalid_messages() {
+        for (message, expected) in [
+            (
+                "to=2&from=1&amount=100",
+                TransferError::MissingField("from"),
+            ),
+            ("from=1&to=2&amount=x1", TransferError::InvalidAmount),
+            ("from=1&to=&amount=1", TransferError::InvalidAccount),
+            ("from=1&to=2&amount=1&to=3", TransferError::TrailingData),
+        ] {
+            let result = Transfer::parse(&Bytes::from_ascii(message));
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                expected.to_string(),
+                "{message}"
+            );
+        }
+        assert!(TransferList::parse(&Bytes::from_ascii("from=1&tx_list=2")).is_err());
+
+        // trailing garbage after an amount is ignored
+        let transfer = Transfer::parse(&Bytes::from_ascii("from=1&to=2&amount=12ab")).unwrap();
+        assert_eq!(*transfer.amount(), 12);
+    }
+}
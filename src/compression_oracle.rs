@@ -0,0 +1,178 @@
+use std::{cell::Cell, io::Write};
+
+use flate2::{write::DeflateEncoder, Compression};
+use rand::random;
+
+use crate::{Bytes, Key, Mode, AES, IV};
+
+/// the cipher which the compression oracle uses after compressing the request
+#[derive(Copy, Clone, Debug)]
+pub enum OracleCipher {
+    Ctr,
+    Cbc,
+}
+
+/// an oracle which formats a request containing a secret session id,
+/// compresses and encrypts it under a fresh key, and returns only the length
+/// of the ciphertext (challenge 51)
+pub struct CompressionOracle {
+    cipher: OracleCipher,
+    session_id: String,
+    queries: Cell<usize>,
+}
+
+impl CompressionOracle {
+    pub fn new(cipher: OracleCipher, session_id: &str) -> Self {
+        Self {
+            cipher,
+            session_id: session_id.to_owned(),
+            queries: Cell::new(0),
+        }
+    }
+
+    /// the number of queries this oracle has answered so far
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+
+    fn format_request(&self, payload: &[u8]) -> Vec<u8> {
+        let mut request = format!(
+            "POST / HTTP/1.1\n\
+            Host: hapless.com\n\
+            Cookie: sessionid={}\n\
+            Content-Length: {}\n",
+            self.session_id,
+            payload.len()
+        )
+        .into_bytes();
+        request.extend(payload);
+        request
+    }
+
+    /// returns the length of the compressed and encrypted request
+    pub fn query(&self, payload: &[u8]) -> usize {
+        self.queries.set(self.queries.get() + 1);
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&self.format_request(payload))
+            .expect("writing into a Vec does not fail");
+        let compressed = Bytes::from(encoder.finish().expect("writing into a Vec does not fail"));
+
+        let key = Key::random_128();
+        let encrypted = match self.cipher {
+            OracleCipher::Ctr => compressed.aes_ctr(&key, random()),
+            OracleCipher::Cbc => compressed.aes_cbc(Mode::Encrypt, &key, &random::<IV>()),
+        };
+        encrypted
+            .expect("encryption with a valid key does not fail")
+            .len()
+    }
+}
+
+const SESSION_ID_ALPHABET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/=\n";
+
+/// characters which do not occur in the request, and which are used to shift
+/// the compressed length across byte and block boundaries
+const FILLER: &[u8] = b"!@#$%^&*()-_[]{}<>~|;";
+
+/// the part of the request in front of the session id, which is known to the
+/// attacker
+const KNOWN_PREFIX: &[u8] = b"hapless.com\nCookie: sessionid=";
+
+/// the number of known characters in front of each guess
+///
+/// Deflate encodes match lengths from 19 to 22 with the same symbol and two
+/// extra bits, so a correct guess extends the match from 20 to 21 characters
+/// without changing its cost, while a wrong guess adds a literal.
+const WINDOW: usize = 20;
+
+/// the number of filler orderings after which a unique best guess is accepted
+const MIN_VARIANTS: usize = 4;
+
+/// the number of filler orderings after which the best guess is accepted, even
+/// if it is not unique
+const MAX_VARIANTS: usize = 8;
+
+/// the number of candidates which are kept after each filler ordering
+const KEPT_CANDIDATES: usize = 16;
+
+/// recovers the session id from a compression oracle
+///
+/// For every position, we append each character of the base64 alphabet to
+/// the last [`WINDOW`] known characters of the request and keep the guess
+/// which compresses best, because a correct guess extends the match with the
+/// cookie. The lengths are only measured with a granularity of bytes (or even
+/// blocks, if the oracle uses CBC), so we put increasing numbers of
+/// incompressible filler characters in front of the payload, which shift the
+/// compressed length across byte and block boundaries, and sum up the
+/// lengths. This is repeated with different orderings of the filler until
+/// there is a unique best guess.
+///
+/// The session id is terminated by the line break after it; recovery stops
+/// when the line break is the best guess, or after `max_length` characters.
+pub fn recover_session_id(oracle: &CompressionOracle, max_length: usize) -> String {
+    let mut known = KNOWN_PREFIX.to_vec();
+
+    while known.len() < KNOWN_PREFIX.len() + max_length {
+        let best = best_candidate(oracle, &known[known.len() - WINDOW..], SESSION_ID_ALPHABET);
+        if best == b'\n' {
+            break;
+        }
+        known.push(best);
+    }
+
+    String::from_utf8_lossy(&known[KNOWN_PREFIX.len()..]).to_string()
+}
+
+/// ranks the candidates by their accumulated compressed length over
+/// different filler orderings, dropping the worst candidates as we go, and
+/// returns the best one
+fn best_candidate(oracle: &CompressionOracle, window: &[u8], candidates: &[u8]) -> u8 {
+    let mut scores: Vec<(usize, u8)> = candidates.iter().map(|c| (0, *c)).collect();
+
+    for variant in 0..MAX_VARIANTS {
+        let mut filler = FILLER.to_vec();
+        filler.rotate_left(5 * variant % FILLER.len());
+
+        for (score, candidate) in scores.iter_mut() {
+            *score += (0..=filler.len())
+                .map(|length| oracle.query(&[&filler[..length], window, &[*candidate]].concat()))
+                .sum::<usize>();
+        }
+        scores.sort();
+
+        let unique = scores.len() == 1 || scores[0].0 < scores[1].0;
+        if unique && variant + 1 >= MIN_VARIANTS {
+            break;
+        }
+
+        if scores.len() > KEPT_CANDIDATES {
+            let threshold = scores[KEPT_CANDIDATES - 1].0;
+            scores.retain(|(score, _)| *score <= threshold);
+        }
+    }
+
+    scores[0].1
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{recover_session_id, CompressionOracle, OracleCipher};
+
+    // shorter than the session id of the challenge, which takes a while
+    const SESSION_ID: &str = "q8Rv+2LxN0dW";
+
+    #[test]
+    fn test_ctr() {
+        let oracle = CompressionOracle::new(OracleCipher::Ctr, SESSION_ID);
+        assert_eq!(recover_session_id(&oracle, 64), SESSION_ID);
+    }
+
+    #[test]
+    fn test_cbc() {
+        let oracle = CompressionOracle::new(OracleCipher::Cbc, SESSION_ID);
+        assert_eq!(recover_session_id(&oracle, 64), SESSION_ID);
+    }
+}
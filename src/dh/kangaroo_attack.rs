@@ -0,0 +1,66 @@
+use num_bigint::BigUint;
+
+use crate::{
+    invmod, kangaroo, subgroup_confinement_residues, DhGroup, DhMacBot, KangarooParameters,
+};
+
+/// recovers the bot's private key by combining the small subgroup
+/// confinement attack with the kangaroo algorithm (challenge 58)
+///
+/// The confinement attack gives us `x = n mod r`, so `x = n + m * r` for an
+/// unknown `m` in `[0, (q - 1) / r]`. With `y' = y * g^-n = (g^r)^m`, we
+/// search for `m` with the kangaroo algorithm in the group generated by
+/// `g^r`. Returns `None` if the kangaroo algorithm fails repeatedly.
+///
+/// ```rust
+/// use cryptopals::{recover_key_with_kangaroo, DhGroup, DhMacBot};
+/// use num_bigint::BigUint;
+/// let x = BigUint::parse_bytes(b"271828182845904523536028747135266249775", 10).unwrap();
+/// let bot = DhMacBot::with_private_key(DhGroup::challenge_58(), x.clone());
+/// assert_eq!(recover_key_with_kangaroo(&bot), Some(x));
+/// ```
+pub fn recover_key_with_kangaroo(bot: &DhMacBot) -> Option<BigUint> {
+    let group = bot.group();
+    let (n, r) = subgroup_confinement_residues(bot, group.q());
+    if r > *group.q() {
+        return Some(n);
+    }
+
+    let p = group.p();
+    let y = bot.public_key() * invmod(&group.public_key(&n), p)? % p;
+    let confined = DhGroup::new(p.clone(), group.q().clone(), group.public_key(&r));
+
+    let upper = (group.q() - 1u32) / &r;
+    let parameters = KangarooParameters::for_interval(&upper);
+
+    // the kangaroos miss each other every now and then, so we retry with
+    // different jump functions
+    let m = (0..3).find_map(|i| {
+        let parameters = KangarooParameters::new(parameters.k + i);
+        kangaroo(&confined, &y, &BigUint::ZERO, &upper, parameters)
+    })?;
+    Some(n + m * r)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use crate::{kangaroo, DhGroup, KangarooParameters};
+
+    #[test]
+    fn test_kangaroo() {
+        let group = DhGroup::challenge_58();
+        let a = BigUint::from(1000u32);
+        let b = BigUint::from(1u32 << 20);
+        let parameters = KangarooParameters::for_interval(&(&b - &a));
+
+        // the algorithm is deterministic, but may miss for unlucky keys, so
+        // the keys are fixed
+        for x in [1000u32, 31337, 500_000, 777_777, (1 << 20) - 1, 1 << 20] {
+            let x = BigUint::from(x);
+            let y = group.public_key(&x);
+            assert_eq!(kangaroo(&group, &y, &a, &b, parameters), Some(x));
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use getset::Getters;
+use lazy_static::lazy_static;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::One;
+
+use crate::{low_bits, KangarooGroup};
+
+mod subgroup_confinement;
+pub use subgroup_confinement::*;
+
+mod kangaroo_attack;
+pub use kangaroo_attack::*;
+
+/// the message the MAC bots of challenges 57 to 60 send along with its MAC
+pub(crate) const BOT_MESSAGE: &str = "crazy flamboyant for the rap enjoyment";
+
+lazy_static! {
+    /// the NIST prime used in challenges 33 and following
+    pub static ref NIST_PRIME: BigUint = BigUint::parse_bytes(
+        b"ffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024\
+        e088a67cc74020bbea63b139b22514a08798e3404ddef9519b3cd\
+        3a431b302b0a6df25f14374fe1356d6d51c245e485b576625e7ec\
+        6f44c42e9a637ed6b0bff5cb6f406b7edee386bfb5a899fa5ae9f\
+        24117c4b1fe649286651ece45b3dc2007cb8a163bf0598da48361\
+        c55d39a69163fa8fd24cf5f83655d23dca3ad961c62f356208552\
+        bb9ed529077096966d670c354e4abc9804f1746c08ca237327fff\
+        fffffffffffff",
+        16
+    )
+    .unwrap();
+
+    /// the generator that goes along with [`NIST_PRIME`]
+    pub static ref NIST_GENERATOR: BigUint = BigUint::from(2u32);
+
+    static ref CHALLENGE_57_P: BigUint = BigUint::parse_bytes(
+        b"7199773997391911030609999317773941274322764333428698921736339643928346453700085358802973900485592910475480089726140708102474957429903531369589969318716771",
+        10
+    )
+    .unwrap();
+    static ref CHALLENGE_57_G: BigUint = BigUint::parse_bytes(
+        b"4565356397095740655436854503483826832136106141639563487732438195343690437606117828318042418238184896212352329118608100083187535033402010599512641674644143",
+        10
+    )
+    .unwrap();
+    static ref CHALLENGE_57_Q: BigUint =
+        BigUint::parse_bytes(b"236234353446506858198510045061214171961", 10).unwrap();
+
+    static ref CHALLENGE_58_P: BigUint = BigUint::parse_bytes(
+        b"11470374874925275658116663507232161402086650258453896274534991676898999262641581519101074740642369848233294239851519212341844337347119899874391456329785623",
+        10
+    )
+    .unwrap();
+    static ref CHALLENGE_58_G: BigUint = BigUint::parse_bytes(
+        b"622952335333961296978159266084741085889881358738459939978290179936063635566740258555167783009058567397963466103140082647486611657350811560630587013183357",
+        10
+    )
+    .unwrap();
+    static ref CHALLENGE_58_Q: BigUint =
+        BigUint::parse_bytes(b"335062023296420808191071248367701059461", 10).unwrap();
+}
+
+/// a subgroup of prime order `q` of the multiplicative group modulo `p`,
+/// which is generated by `g`
+#[derive(Getters, Clone, Debug, Eq, PartialEq)]
+#[getset(get = "pub")]
+pub struct DhGroup {
+    p: BigUint,
+    q: BigUint,
+    g: BigUint,
+}
+
+impl DhGroup {
+    pub fn new(p: BigUint, q: BigUint, g: BigUint) -> Self {
+        Self { p, q, g }
+    }
+
+    /// the group of challenge 57, where `(p - 1) / q` has many small factors
+    pub fn challenge_57() -> Self {
+        Self::new(
+            CHALLENGE_57_P.clone(),
+            CHALLENGE_57_Q.clone(),
+            CHALLENGE_57_G.clone(),
+        )
+    }
+
+    /// the group of challenge 58, where the small factors of `(p - 1) / q`
+    /// do not cover `q`
+    pub fn challenge_58() -> Self {
+        Self::new(
+            CHALLENGE_58_P.clone(),
+            CHALLENGE_58_Q.clone(),
+            CHALLENGE_58_G.clone(),
+        )
+    }
+
+    /// generates a random private key in the range `1..q`
+    pub fn generate_private_key(&self) -> BigUint {
+        rand::thread_rng().gen_biguint_range(&BigUint::one(), &self.q)
+    }
+
+    /// calculates `g^x mod p`
+    pub fn public_key(&self, x: &BigUint) -> BigUint {
+        self.g.modpow(x, &self.p)
+    }
+
+    /// finds a random element of order `r` modulo `p`, where `r` is a prime
+    /// dividing `p - 1`
+    pub fn element_of_order(&self, r: &BigUint) -> BigUint {
+        let exponent = (&self.p - 1u32) / r;
+        loop {
+            let candidate = rand::thread_rng().gen_biguint_range(&BigUint::one(), &self.p);
+            let element = candidate.modpow(&exponent, &self.p);
+            if !element.is_one() {
+                return element;
+            }
+        }
+    }
+}
+
+impl KangarooGroup for DhGroup {
+    type Element = BigUint;
+
+    fn combine(&self, lhs: &BigUint, rhs: &BigUint) -> BigUint {
+        lhs * rhs % &self.p
+    }
+
+    fn generator_power(&self, exponent: &BigUint) -> BigUint {
+        self.public_key(exponent)
+    }
+
+    fn jump_selector(&self, element: &BigUint) -> u64 {
+        low_bits(element)
+    }
+}
@@ -0,0 +1,127 @@
+use num_bigint::BigUint;
+use num_traits::One;
+
+use super::BOT_MESSAGE;
+use crate::{crt, hmac_sha256, small_prime_factors, Bytes, DhGroup};
+
+/// the bound for the prime factors of `(p - 1) / q` which the attacks use
+pub const SMALL_FACTOR_BOUND: u64 = 1 << 16;
+
+/// authenticates `message` with HMAC-SHA256 under the shared key `key`
+pub fn dh_mac(key: &BigUint, message: &[u8]) -> Bytes {
+    hmac_sha256(&Bytes::from(key)[..], message)
+}
+
+/// a bot which completes a Diffie-Hellman key exchange with any public key
+/// it receives, without validating it, and answers with a message and its
+/// MAC under the shared key (challenge 57)
+pub struct DhMacBot {
+    group: DhGroup,
+    x: BigUint,
+}
+
+impl DhMacBot {
+    pub fn new(group: DhGroup) -> Self {
+        let x = group.generate_private_key();
+        Self::with_private_key(group, x)
+    }
+
+    pub fn with_private_key(group: DhGroup, x: BigUint) -> Self {
+        Self { group, x }
+    }
+
+    pub fn group(&self) -> &DhGroup {
+        &self.group
+    }
+
+    pub fn public_key(&self) -> BigUint {
+        self.group.public_key(&self.x)
+    }
+
+    /// derives the shared key from `public_key` and returns the message and
+    /// its MAC
+    pub fn respond(&self, public_key: &BigUint) -> (Bytes, Bytes) {
+        let shared_key = public_key.modpow(&self.x, self.group.p());
+        let message = Bytes::from_ascii(BOT_MESSAGE);
+        let mac = dh_mac(&shared_key, &message[..]);
+        (message, mac)
+    }
+}
+
+/// learns the bot's private key modulo small primes dividing `(p - 1) / q`,
+/// until the product of the primes exceeds `target`, or all of them are used
+///
+/// For every prime `r`, we send an element `h` of order `r` as public key.
+/// The shared key `h^x` is then confined to the `r` powers of `h`, so we find
+/// `x mod r` by checking the MAC for each of them.
+///
+/// Returns `x mod m` and `m`, where `m` is the product of the primes.
+pub fn subgroup_confinement_residues(bot: &DhMacBot, target: &BigUint) -> (BigUint, BigUint) {
+    let group = bot.group();
+    let cofactor = (group.p() - 1u32) / group.q();
+
+    let mut residues = Vec::new();
+    let mut modulus = BigUint::one();
+    for r in small_prime_factors(&cofactor, SMALL_FACTOR_BOUND) {
+        if modulus > *target {
+            break;
+        }
+        let h = group.element_of_order(&BigUint::from(r));
+        let (message, mac) = bot.respond(&h);
+
+        let mut shared_key = BigUint::one();
+        for residue in 0..r {
+            if dh_mac(&shared_key, &message[..]) == mac {
+                residues.push((BigUint::from(residue), BigUint::from(r)));
+                modulus *= r;
+                break;
+            }
+            shared_key = shared_key * &h % group.p();
+        }
+    }
+
+    let residue = crt(&residues).expect("distinct primes are coprime");
+    (residue, modulus)
+}
+
+/// recovers the bot's private key with a small subgroup confinement attack,
+/// if the small factors of `(p - 1) / q` multiply to more than `q`
+///
+/// ```rust
+/// use cryptopals::{recover_key_by_subgroup_confinement, DhGroup, DhMacBot};
+/// let bot = DhMacBot::new(DhGroup::challenge_57());
+/// let x = recover_key_by_subgroup_confinement(&bot).unwrap();
+/// assert_eq!(bot.group().public_key(&x), bot.public_key());
+/// ```
+pub fn recover_key_by_subgroup_confinement(bot: &DhMacBot) -> Option<BigUint> {
+    let q = bot.group().q();
+    let (x, modulus) = subgroup_confinement_residues(bot, q);
+    (modulus > *q).then_some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use num_traits::{One, Zero};
+
+    use crate::{small_prime_factors, DhGroup, SMALL_FACTOR_BOUND};
+
+    #[test]
+    fn test_challenge_57_group() {
+        let group = DhGroup::challenge_57();
+        let p_minus_one = group.p() - 1u32;
+        assert!((&p_minus_one % group.q()).is_zero());
+        assert!(group.g().modpow(group.q(), group.p()).is_one());
+
+        let cofactor = p_minus_one / group.q();
+        let factors = small_prime_factors(&cofactor, SMALL_FACTOR_BOUND);
+        assert_eq!(
+            factors,
+            [2, 3, 5, 109, 7963, 8539, 20641, 38833, 39341, 46337, 51977, 54319, 57529]
+        );
+        for r in factors {
+            let h = group.element_of_order(&BigUint::from(r));
+            assert!(h.modpow(&BigUint::from(r), group.p()).is_one());
+        }
+    }
+}
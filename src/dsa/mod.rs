@@ -0,0 +1,229 @@
+use getset::Getters;
+use lazy_static::lazy_static;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use openssl::sha::sha1;
+use thiserror::Error;
+
+use crate::{invmod, is_probable_prime, Bytes};
+
+mod nonce_recovery;
+pub use nonce_recovery::*;
+
+mod signature_dump;
+pub use signature_dump::*;
+
+mod repeated_nonce;
+pub use repeated_nonce::*;
+
+mod parameter_tampering;
+pub use parameter_tampering::*;
+
+lazy_static! {
+    static ref CHALLENGE_P: BigUint = BigUint::parse_bytes(
+        b"800000000000000089e1855218a0e7dac38136ffafa72eda7\
+        859f2171e25e65eac698c1702578b07dc2a1076da241c76c6\
+        2d374d8389ea5aeffd3226a0530cc565f3bf6b50929139ebe\
+        ac04f48c3c84afb796d61e5a4f9a8fda812ab59494232c7d2\
+        b4deb50aa18ee9e132bfa85ac4374d7f9091abc3d015efc87\
+        1a584471bb1",
+        16
+    )
+    .unwrap();
+    static ref CHALLENGE_Q: BigUint =
+        BigUint::parse_bytes(b"f4f47f05794b256174bba6e9b396a7707e563c5b", 16).unwrap();
+    static ref CHALLENGE_G: BigUint = BigUint::parse_bytes(
+        b"5958c9d3898b224b12672c0b98e06c60df923cb8bc999d11\
+        9458fef538b8fa4046c8db53039db620c094c9fa077ef389b5\
+        322a559946a71903f990f1f7e0e025e2d7f7cf494aff1a0470\
+        f5b64c36b625a097f1651fe775323556fe00b3608c88789287\
+        8480e99041be601a62166ca6894bdd41a7054ec89f756ba9fc\
+        95302291",
+        16
+    )
+    .unwrap();
+}
+
+#[derive(Error, Debug)]
+pub enum DsaParameterError {
+    #[error("p and q must be prime")]
+    NotPrime,
+
+    #[error("q must divide p - 1")]
+    InvalidSubgroupOrder,
+
+    #[error("g must generate a subgroup of order q")]
+    InvalidGenerator,
+}
+
+/// the domain parameters `(p, q, g)` of DSA
+#[derive(Getters, Clone, Debug, Eq, PartialEq)]
+#[getset(get = "pub")]
+pub struct DsaParameters {
+    p: BigUint,
+    q: BigUint,
+    g: BigUint,
+}
+
+impl DsaParameters {
+    pub fn new(p: BigUint, q: BigUint, g: BigUint) -> Self {
+        Self { p, q, g }
+    }
+
+    /// checks that `p` and `q` are prime, that `q` divides `p - 1` and that
+    /// `g` generates a subgroup of order `q`
+    pub fn validate(&self) -> Result<(), DsaParameterError> {
+        if !is_probable_prime(&self.p) || !is_probable_prime(&self.q) {
+            return Err(DsaParameterError::NotPrime);
+        }
+        if !((&self.p - 1u32) % &self.q).is_zero() {
+            return Err(DsaParameterError::InvalidSubgroupOrder);
+        }
+        let g_in_range = BigUint::one() < self.g && self.g < self.p;
+        if !g_in_range || !self.g.modpow(&self.q, &self.p).is_one() {
+            return Err(DsaParameterError::InvalidGenerator);
+        }
+        Ok(())
+    }
+}
+
+impl Default for DsaParameters {
+    /// the parameters used in challenges 43 to 45
+    fn default() -> Self {
+        Self::new(
+            CHALLENGE_P.clone(),
+            CHALLENGE_Q.clone(),
+            CHALLENGE_G.clone(),
+        )
+    }
+}
+
+#[derive(Getters, Clone, Debug, Eq, PartialEq, Hash)]
+#[getset(get = "pub")]
+pub struct DsaSignature {
+    r: BigUint,
+    s: BigUint,
+}
+
+impl DsaSignature {
+    pub fn new(r: BigUint, s: BigUint) -> Self {
+        Self { r, s }
+    }
+}
+
+#[derive(Getters, Clone, Debug, Eq, PartialEq)]
+#[getset(get = "pub")]
+pub struct DsaPublicKey {
+    parameters: DsaParameters,
+    y: BigUint,
+}
+
+#[derive(Getters, Clone, Debug, Eq, PartialEq)]
+#[getset(get = "pub")]
+pub struct DsaPrivateKey {
+    parameters: DsaParameters,
+    x: BigUint,
+    y: BigUint,
+}
+
+/// calculates the SHA-1 hash of `message`, interpreted as integer
+pub fn dsa_hash(message: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(&sha1(message))
+}
+
+impl DsaPublicKey {
+    pub fn new(parameters: DsaParameters, y: BigUint) -> Self {
+        Self { parameters, y }
+    }
+
+    /// verifies `signature` using the domain parameters of this key as they
+    /// are, without checking either the parameters or if `r` and `s` are in
+    /// the range `1..q`
+    ///
+    /// See [`DsaPublicKey::verify_strict`] for a verifier which does these checks.
+    pub fn verify(&self, message: &[u8], signature: &DsaSignature) -> bool {
+        let DsaParameters { p, q, g } = &self.parameters;
+        let DsaSignature { r, s } = signature;
+
+        let w = match invmod(s, q) {
+            Some(w) => w,
+            None => return false,
+        };
+        let u1 = dsa_hash(message) * &w % q;
+        let u2 = r * &w % q;
+        let v = (g.modpow(&u1, p) * self.y.modpow(&u2, p)) % p % q;
+
+        v == *r
+    }
+
+    /// verifies `signature` after checking that the domain parameters are
+    /// valid and that `r` and `s` are in the range `1..q`
+    pub fn verify_strict(&self, message: &[u8], signature: &DsaSignature) -> bool {
+        let q = self.parameters.q();
+        let DsaSignature { r, s } = signature;
+
+        if self.parameters.validate().is_err() || r.is_zero() || r >= q || s.is_zero() || s >= q {
+            return false;
+        }
+
+        self.verify(message, signature)
+    }
+}
+
+impl DsaPrivateKey {
+    pub fn generate(parameters: DsaParameters) -> Self {
+        let x = rand::thread_rng().gen_biguint_range(&BigUint::one(), parameters.q());
+        Self::from_x(parameters, x)
+    }
+
+    pub fn from_x(parameters: DsaParameters, x: BigUint) -> Self {
+        let y = parameters.g().modpow(&x, parameters.p());
+        Self { parameters, x, y }
+    }
+
+    pub fn public_key(&self) -> DsaPublicKey {
+        DsaPublicKey::new(self.parameters.clone(), self.y.clone())
+    }
+
+    /// signs `message` with a random nonce
+    ///
+    /// ```rust
+    /// use cryptopals::{DsaParameters, DsaPrivateKey};
+    /// let key = DsaPrivateKey::generate(DsaParameters::default());
+    /// let signature = key.sign(b"hello, world");
+    /// assert!(key.public_key().verify(b"hello, world", &signature));
+    /// assert!(!key.public_key().verify(b"goodbye, world", &signature));
+    /// ```
+    pub fn sign(&self, message: &[u8]) -> DsaSignature {
+        loop {
+            let k = rand::thread_rng().gen_biguint_range(&BigUint::one(), self.parameters.q());
+            if let Some(signature) = self.sign_with_nonce(message, &k) {
+                return signature;
+            }
+        }
+    }
+
+    /// signs `message` with the nonce `k`; returns `None` if `k` is not
+    /// suitable to create a valid signature
+    pub fn sign_with_nonce(&self, message: &[u8], k: &BigUint) -> Option<DsaSignature> {
+        let DsaParameters { p, q, g } = &self.parameters;
+
+        let r = g.modpow(k, p) % q;
+        if r.is_zero() {
+            return None;
+        }
+
+        let s = invmod(k, q)? * (dsa_hash(message) + &self.x * &r) % q;
+        if s.is_zero() {
+            return None;
+        }
+
+        Some(DsaSignature::new(r, s))
+    }
+
+    /// the SHA-1 hash of the hexadecimal representation of `x`, which is
+    /// used in challenges 43 and 44 to check the recovered keys
+    pub fn fingerprint(&self) -> Bytes {
+        Bytes::from(&sha1(self.x.to_str_radix(16).as_bytes())[..])
+    }
+}
@@ -0,0 +1,114 @@
+use std::ops::Range;
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+
+use crate::{dsa_hash, invmod, DsaPrivateKey, DsaPublicKey, DsaSignature};
+
+/// calculates the private key from a signature, if the nonce `k` is known:
+/// `x = (s * k - H(m)) / r mod q`
+pub fn private_key_from_nonce(
+    public_key: &DsaPublicKey,
+    message_hash: &BigUint,
+    signature: &DsaSignature,
+    k: &BigUint,
+) -> Option<DsaPrivateKey> {
+    let q = public_key.parameters().q();
+    let sk = BigInt::from_biguint(Sign::Plus, signature.s() * k);
+    let numerator = (sk - BigInt::from_biguint(Sign::Plus, message_hash.clone()))
+        .mod_floor(&BigInt::from_biguint(Sign::Plus, q.clone()))
+        .to_biguint()?;
+    let x = numerator * invmod(signature.r(), q)? % q;
+
+    let candidate = DsaPrivateKey::from_x(public_key.parameters().clone(), x);
+    if candidate.y() == public_key.y() {
+        Some(candidate)
+    } else {
+        None
+    }
+}
+
+/// recovers the private key from a signature whose nonce was chosen from
+/// `nonce_range` (challenge 43)
+///
+/// Instead of trying every `k` with a full signature calculation, we walk
+/// through `g**k` with a single multiplication per step and compare with `r`.
+pub fn recover_key_from_bounded_nonce(
+    public_key: &DsaPublicKey,
+    message: &[u8],
+    signature: &DsaSignature,
+    nonce_range: Range<u32>,
+) -> Option<DsaPrivateKey> {
+    let parameters = public_key.parameters();
+    let (p, q, g) = (parameters.p(), parameters.q(), parameters.g());
+    let message_hash = dsa_hash(message);
+
+    let mut g_k = g.modpow(&BigUint::from(nonce_range.start), p);
+    for k in nonce_range {
+        if g_k.clone() % q == *signature.r() {
+            if let Some(key) =
+                private_key_from_nonce(public_key, &message_hash, signature, &k.into())
+            {
+                return Some(key);
+            }
+        }
+        g_k = g_k * g % p;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use crate::{
+        recover_key_from_bounded_nonce, Bytes, DsaParameters, DsaPrivateKey, DsaPublicKey,
+        DsaSignature,
+    };
+
+    #[test]
+    fn test_challenge_43() {
+        let y = BigUint::parse_bytes(
+            b"84ad4719d044495496a3201c8ff484feb45b962e7302e56a392aee4\
+            abab3e4bdebf2955b4736012f21a08084056b19bcd7fee56048e004\
+            e44984e2f411788efdc837a0d2e5abb7b555039fd243ac01f0fb2ed\
+            1dec568280ce678e931868d23eb095fde9d3779191b8c0299d6e07b\
+            bb283e6633451e535c45513b2d33c99ea17",
+            16,
+        )
+        .unwrap();
+        let public_key = DsaPublicKey::new(DsaParameters::default(), y);
+        let message = b"For those that envy a MC it can be hazardous to your health\n\
+        So be friendly, a matter of life and death, just like a etch-a-sketch\n";
+        let signature = DsaSignature::new(
+            BigUint::parse_bytes(b"548099063082341131477253921760299949438196259240", 10).unwrap(),
+            BigUint::parse_bytes(b"857042759984254168557880549501802188789837994940", 10).unwrap(),
+        );
+        assert!(public_key.verify(message, &signature));
+
+        let key =
+            recover_key_from_bounded_nonce(&public_key, message, &signature, 0..(1 << 16)).unwrap();
+        assert_eq!(
+            key.fingerprint(),
+            Bytes::from_hex("0954edd5e0afe5542a4adf012611a91912a3ec16").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_generated_key() {
+        let key = DsaPrivateKey::generate(DsaParameters::default());
+        let signature = key
+            .sign_with_nonce(b"hello", &BigUint::from(4711u32))
+            .unwrap();
+
+        let recovered =
+            recover_key_from_bounded_nonce(&key.public_key(), b"hello", &signature, 1..5000)
+                .unwrap();
+        assert_eq!(recovered, key);
+
+        assert!(
+            recover_key_from_bounded_nonce(&key.public_key(), b"hello", &signature, 1..4711)
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+
+use crate::{invmod, DsaParameters, DsaPublicKey, DsaSignature};
+
+/// the parameters of `honest` with the generator replaced by `p + 1`, which
+/// is congruent to `1 mod p` (challenge 45)
+pub fn tampered_parameters(honest: &DsaParameters) -> DsaParameters {
+    DsaParameters::new(
+        honest.p().clone(),
+        honest.q().clone(),
+        honest.p() + BigUint::one(),
+    )
+}
+
+/// creates a "magic" signature which is accepted by
+/// [`DsaPublicKey::verify`] for every message, if the key uses `g ≡ 1 mod p`
+///
+/// With `r = (y**z % p) % q` and `s = r / z % q`, the verifier calculates
+/// `v = g**u1 * y**u2 = y**(r * w) = y**z`, independent of the message.
+pub fn magic_signature(public_key: &DsaPublicKey) -> DsaSignature {
+    let parameters = public_key.parameters();
+    let (p, q) = (parameters.p(), parameters.q());
+
+    loop {
+        let z = rand::thread_rng().gen_biguint_range(&BigUint::one(), q);
+        let r = public_key.y().modpow(&z, p) % q;
+        if r.is_zero() {
+            continue;
+        }
+        if let Some(z_inverse) = invmod(&z, q) {
+            return DsaSignature::new(r.clone(), r * z_inverse % q);
+        }
+    }
+}
+
+/// creates a signature which is accepted by [`DsaPublicKey::verify`] for
+/// every message, if the key uses `g = 0`: every `v` and therefore `r` will be `0`
+pub fn magic_signature_zero_generator(public_key: &DsaPublicKey) -> DsaSignature {
+    let s = rand::thread_rng().gen_biguint_range(&BigUint::one(), public_key.parameters().q());
+    DsaSignature::new(BigUint::zero(), s)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use crate::{
+        magic_signature, magic_signature_zero_generator, tampered_parameters, DsaParameters,
+        DsaPrivateKey, DsaPublicKey,
+    };
+
+    #[test]
+    fn test_honest_parameters_are_valid() {
+        let key = DsaPrivateKey::generate(DsaParameters::default());
+        let signature = key.sign(b"hello");
+        assert!(key.public_key().verify_strict(b"hello", &signature));
+    }
+
+    #[test]
+    fn test_magic_signature() {
+        let key = DsaPrivateKey::generate(DsaParameters::default());
+        let public_key = DsaPublicKey::new(
+            tampered_parameters(key.parameters()),
+            key.public_key().y().clone(),
+        );
+        let signature = magic_signature(&public_key);
+
+        for message in [&b"Hello, world"[..], b"Goodbye, world"] {
+            assert!(public_key.verify(message, &signature));
+            assert!(!public_key.verify_strict(message, &signature));
+        }
+    }
+
+    #[test]
+    fn test_zero_generator() {
+        let honest = DsaParameters::default();
+        let parameters =
+            DsaParameters::new(honest.p().clone(), honest.q().clone(), BigUint::from(0u32));
+        let key = DsaPrivateKey::generate(DsaParameters::default());
+        let public_key = DsaPublicKey::new(parameters, key.public_key().y().clone());
+        let signature = magic_signature_zero_generator(&public_key);
+
+        for message in [&b"Hello, world"[..], b"Goodbye, world"] {
+            assert!(public_key.verify(message, &signature));
+            assert!(!public_key.verify_strict(message, &signature));
+        }
+    }
+}
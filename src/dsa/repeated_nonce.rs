@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+
+use crate::{invmod, private_key_from_nonce, DsaPrivateKey, DsaPublicKey, SignedMessage};
+
+/// recovers the private key from a set of signatures, if at least two of
+/// them have been created with the same nonce (challenge 44)
+///
+/// Signatures which share a nonce share the same `r`, and for these the nonce
+/// can be calculated as `k = (m1 - m2) / (s1 - s2) mod q`.
+pub fn recover_key_from_repeated_nonce(
+    public_key: &DsaPublicKey,
+    messages: &[SignedMessage],
+) -> Option<DsaPrivateKey> {
+    let q = public_key.parameters().q();
+    let q_signed = BigInt::from_biguint(Sign::Plus, q.clone());
+    let to_signed = |value| BigInt::from_biguint(Sign::Plus, value);
+
+    let mut by_r: HashMap<_, Vec<&SignedMessage>> = HashMap::new();
+    for message in messages {
+        by_r.entry(message.signature().r())
+            .or_default()
+            .push(message);
+    }
+
+    for group in by_r.values().filter(|group| group.len() > 1) {
+        for (idx, first) in group.iter().enumerate() {
+            for second in &group[idx + 1..] {
+                let hash_difference = (to_signed(first.hash().clone())
+                    - to_signed(second.hash().clone()))
+                .mod_floor(&q_signed)
+                .to_biguint()?;
+                let s_difference = (to_signed(first.signature().s().clone())
+                    - to_signed(second.signature().s().clone()))
+                .mod_floor(&q_signed)
+                .to_biguint()?;
+
+                let Some(s_inverse) = invmod(&s_difference, q) else {
+                    continue;
+                };
+                let k = hash_difference * s_inverse % q;
+
+                if let Some(key) =
+                    private_key_from_nonce(public_key, first.hash(), first.signature(), &k)
+                {
+                    return Some(key);
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use num_bigint::BigUint;
+
+    use crate::{
+        dsa_hash, recover_key_from_repeated_nonce, Bytes, DsaParameters, DsaPublicKey,
+        SignatureDump,
+    };
+
+    #[test]
+    fn test_recover_from_dump() {
+        let dump: SignatureDump = fs::read_to_string("data/44.txt")
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(dump.messages().len(), 11);
+
+        let y = BigUint::parse_bytes(CHALLENGE_PUBLIC_KEY.as_bytes(), 16).unwrap();
+        let public_key = DsaPublicKey::new(DsaParameters::default(), y);
+        for message in dump.messages() {
+            assert_eq!(*message.hash(), dsa_hash(message.message().as_bytes()));
+            assert!(public_key.verify(message.message().as_bytes(), message.signature()));
+        }
+
+        let key = recover_key_from_repeated_nonce(&public_key, dump.messages()).unwrap();
+        assert_eq!(
+            key.fingerprint(),
+            Bytes::from_hex("ca8f6f7c66fa362d40760d135b763eb8527d3d52").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_hash_from_dump() {
+        // the trailing space of the message has been lost, and the hash lacks its leading zero
+        let dump = "msg: People em say ya come from Jamaica,\ns: 1\nr: 2\n\
+            m: 7d9abd18bbecdaa93650ecc4da1b9fcae911412\n";
+        let dump: SignatureDump = dump.parse().unwrap();
+        assert_eq!(
+            *dump.messages()[0].hash(),
+            BigUint::parse_bytes(b"07d9abd18bbecdaa93650ecc4da1b9fcae911412", 16).unwrap()
+        );
+    }
+
+    /// the public key of challenge 44
+    const CHALLENGE_PUBLIC_KEY: &str = concat!(
+        "2d026f4bf30195ede3a088da85e398ef869611d0f68f0713d51c9c1a3a26c951",
+        "05d915e2d8cdf26d056b86b8a7b85519b1c23cc3ecdc6062650462e3063bd179",
+        "c2a6581519f674a61f1d89a1fff27171ebc1b93d4dc57bceb7ae2430f98a6a4d",
+        "83d8279ee65d71c1203d2c96d65ebbf7cce9d32971c3de5084cce04a2e147821"
+    );
+}
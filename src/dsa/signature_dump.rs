@@ -0,0 +1,97 @@
+use std::str::FromStr;
+
+use getset::Getters;
+use num_bigint::BigUint;
+use thiserror::Error;
+
+use crate::DsaSignature;
+
+#[derive(Error, Debug)]
+pub enum SignatureDumpError {
+    #[error("line {0}: expected field '{1}'")]
+    MissingField(usize, &'static str),
+
+    #[error("unexpected end of input, expected field '{0}'")]
+    UnexpectedEnd(&'static str),
+
+    #[error("line {0}: invalid number")]
+    InvalidNumber(usize),
+}
+
+/// a message together with its DSA signature and its hash
+#[derive(Getters, Clone, Debug)]
+#[getset(get = "pub")]
+pub struct SignedMessage {
+    message: String,
+    signature: DsaSignature,
+    hash: BigUint,
+}
+
+/// a list of signed messages in the format used by challenge 44, where each
+/// message consists of four lines:
+///
+/// ```text
+/// msg: Listen for me, you better listen for me now.
+/// s: 1267396447369736888040262262183731677867615804316
+/// r: 1105520928110492191417703162650245113664610474875
+/// m: a4db3de27e2db3e5ef085ced2bced91b82e0df19
+/// ```
+///
+/// `s` and `r` are decimal numbers, `m` is the hexadecimal SHA-1 hash of
+/// the message. The hash is taken as given and not checked against the
+/// message, because copies of the dump tend to lose the trailing whitespace
+/// of the messages.
+pub struct SignatureDump(Vec<SignedMessage>);
+
+impl SignatureDump {
+    pub fn messages(&self) -> &[SignedMessage] {
+        &self.0[..]
+    }
+}
+
+fn field<'l>(
+    lines: &mut impl Iterator<Item = (usize, &'l str)>,
+    name: &'static str,
+) -> Result<(usize, &'l str), SignatureDumpError> {
+    let (line_number, line) = lines
+        .next()
+        .ok_or(SignatureDumpError::UnexpectedEnd(name))?;
+    let value = line
+        .strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix(": "))
+        .ok_or(SignatureDumpError::MissingField(line_number, name))?;
+    Ok((line_number, value))
+}
+
+fn number((line_number, value): (usize, &str), radix: u32) -> Result<BigUint, SignatureDumpError> {
+    BigUint::parse_bytes(value.trim().as_bytes(), radix)
+        .ok_or(SignatureDumpError::InvalidNumber(line_number))
+}
+
+impl FromStr for SignatureDump {
+    type Err = SignatureDumpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim_end_matches('\r')))
+            .filter(|(_, line)| !line.trim().is_empty())
+            .peekable();
+
+        let mut messages = Vec::new();
+        while lines.peek().is_some() {
+            let (_, message) = field(&mut lines, "msg")?;
+            let s = number(field(&mut lines, "s")?, 10)?;
+            let r = number(field(&mut lines, "r")?, 10)?;
+            let hash = number(field(&mut lines, "m")?, 16)?;
+
+            messages.push(SignedMessage {
+                message: message.to_owned(),
+                signature: DsaSignature::new(r, s),
+                hash,
+            });
+        }
+        Ok(Self(messages))
+    }
+}
@@ -0,0 +1,128 @@
+use num_bigint::{BigInt, BigUint, RandBigInt};
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Signed, Zero};
+
+use crate::{
+    ecdsa_hash, invmod, lll, Bytes, EcGroup, EcdsaPrivateKey, EcdsaPublicKey, EcdsaSignature,
+};
+
+/// the number of low bits of the nonces which the signer leaves zero
+pub const BIASED_NONCE_BITS: u64 = 8;
+
+/// a signer whose nonces are biased: their lowest [`BIASED_NONCE_BITS`] bits
+/// are always zero (challenge 62)
+pub struct BiasedNonceSigner {
+    key: EcdsaPrivateKey,
+}
+
+impl BiasedNonceSigner {
+    pub fn new(group: EcGroup) -> Self {
+        Self {
+            key: EcdsaPrivateKey::generate(group),
+        }
+    }
+
+    pub fn public_key(&self) -> EcdsaPublicKey {
+        self.key.public_key()
+    }
+
+    pub fn sign(&self, message: &[u8]) -> EcdsaSignature {
+        let n = self.key.group().order();
+        loop {
+            let k = rand::thread_rng().gen_biguint_below(&(n >> BIASED_NONCE_BITS));
+            if let Some(signature) = self.key.sign_with_nonce(message, &(k << BIASED_NONCE_BITS)) {
+                return signature;
+            }
+        }
+    }
+}
+
+/// recovers the private key from signatures whose nonces have their lowest
+/// [`BIASED_NONCE_BITS`] bits set to zero, using lattice reduction
+///
+/// With `k = 2^l * b`, the signing equation `s = (H(m) + d * r) / k` becomes
+/// `b = d * t - u mod n`, where `t = r / (s * 2^l)` and `u = -H(m) / (s * 2^l)`
+/// are known and `b < n / 2^l` is small. So the lattice spanned by the rows
+///
+/// ```text
+/// n   0   ... 0   0     0
+/// 0   n   ... 0   0     0
+///         ...
+/// t_1 t_2 ... t_k ct    0
+/// u_1 u_2 ... u_k 0     cu
+/// ```
+///
+/// with `ct = 1 / 2^l` and `cu = n / 2^l` contains the short vector
+/// `(b_1, ..., b_k, d * ct, -cu)`, which LLL finds among its reduced basis
+/// if there are enough signatures: a few more than the number of bits of
+/// `n` divided by `l`, so about 20 for the curve of challenge 59. Returns
+/// `None` if the key is not found.
+pub fn recover_key_from_biased_nonces(
+    public_key: &EcdsaPublicKey,
+    messages: &[(Bytes, EcdsaSignature)],
+) -> Option<EcdsaPrivateKey> {
+    let group = public_key.group();
+    let n = group.order();
+    let shift = BigUint::one() << BIASED_NONCE_BITS;
+    let rational = |value: &BigUint| BigRational::from_integer(BigInt::from(value.clone()));
+
+    let mut ts = Vec::with_capacity(messages.len());
+    let mut us = Vec::with_capacity(messages.len());
+    for (message, signature) in messages {
+        let inverse = invmod(&(signature.s() * &shift % n), n)?;
+        ts.push(signature.r() * &inverse % n);
+        us.push((n - ecdsa_hash(&message[..], n) % n) * inverse % n);
+    }
+
+    let k = messages.len();
+    let ct = rational(&shift).recip();
+    let cu = rational(n) * &ct;
+    let mut basis = vec![vec![BigRational::zero(); k + 2]; k + 2];
+    for i in 0..k {
+        basis[i][i] = rational(n);
+        basis[k][i] = rational(&ts[i]);
+        basis[k + 1][i] = rational(&us[i]);
+    }
+    basis[k][k] = ct;
+    basis[k + 1][k + 1] = cu.clone();
+
+    let delta = BigRational::new(BigInt::from(99), BigInt::from(100));
+    lll(&basis, &delta).into_iter().find_map(|row| {
+        if row[k + 1].abs() != cu {
+            return None;
+        }
+        // the row is the short vector, or its negation
+        let d = &row[k] * rational(&shift);
+        let d = if row[k + 1].is_negative() { d } else { -d };
+        if !d.is_integer() {
+            return None;
+        }
+        let d = d
+            .to_integer()
+            .mod_floor(&BigInt::from(n.clone()))
+            .to_biguint()?;
+        let key = EcdsaPrivateKey::from_d(group.clone(), d);
+        (key.public_key() == *public_key).then_some(key)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{recover_key_from_biased_nonces, BiasedNonceSigner, Bytes, EcGroup};
+
+    #[test]
+    fn test_biased_nonce_attack() {
+        let signer = BiasedNonceSigner::new(EcGroup::challenge_59());
+        let messages: Vec<_> = (0..20)
+            .map(|i| {
+                let message = Bytes::from_ascii(&format!("message number {i}"));
+                let signature = signer.sign(&message[..]);
+                assert!(signer.public_key().verify(&message[..], &signature));
+                (message, signature)
+            })
+            .collect();
+        let key = recover_key_from_biased_nonces(&signer.public_key(), &messages).unwrap();
+        assert_eq!(key.public_key(), signer.public_key());
+    }
+}
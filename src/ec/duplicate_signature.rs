@@ -0,0 +1,78 @@
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::One;
+
+use crate::{
+    ecdsa_hash, ecdsa_verification_point, invmod, EcGroup, EcPoint, EcdsaPrivateKey,
+    EcdsaPublicKey, EcdsaSignature,
+};
+
+/// creates a new key pair under which `signature` of `message` verifies as
+/// well, given a valid signature under `public_key` (challenge 61)
+///
+/// The verifier computes `R = u1 * G + u2 * Q` and compares its `x`
+/// coordinate to `r`, so it accepts any key for which `R` stays the same. We
+/// pick a random private key `d'`, compute `t = u1 + u2 * d'` and use the
+/// generator `G' = R / t`, so `u1 * G' + u2 * d' * G' = t * G' = R`. Returns
+/// `None` if the signature is not valid.
+///
+/// ```rust
+/// use cryptopals::{forge_ecdsa_key, EcGroup, EcdsaPrivateKey};
+/// let key = EcdsaPrivateKey::generate(EcGroup::challenge_59());
+/// let signature = key.sign(b"hello, world");
+/// let forged = forge_ecdsa_key(&key.public_key(), b"hello, world", &signature).unwrap();
+/// assert_ne!(forged.public_key(), key.public_key());
+/// assert!(forged.public_key().verify(b"hello, world", &signature));
+/// ```
+pub fn forge_ecdsa_key(
+    public_key: &EcdsaPublicKey,
+    message: &[u8],
+    signature: &EcdsaSignature,
+) -> Option<EcdsaPrivateKey> {
+    if !public_key.verify(message, signature) {
+        return None;
+    }
+    let target = ecdsa_verification_point(public_key, message, signature)?;
+
+    let group = public_key.group();
+    let n = group.order();
+    let w = invmod(signature.s(), n)?;
+    let u1 = ecdsa_hash(message, n) * &w % n;
+    let u2 = signature.r() * &w % n;
+
+    loop {
+        let d = rand::thread_rng().gen_biguint_range(&BigUint::one(), n);
+        let t = (&u1 + &u2 * &d) % n;
+        let Some(t_inverse) = invmod(&t, n) else {
+            continue;
+        };
+        let generator = group.curve().scalar_mul(&target, &t_inverse);
+        if generator == EcPoint::Infinity {
+            continue;
+        }
+        let forged = EcGroup::new(group.curve().clone(), generator, n.clone());
+        return Some(EcdsaPrivateKey::from_d(forged, d));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{forge_ecdsa_key, EcGroup, EcdsaPrivateKey};
+
+    #[test]
+    fn test_forge_ecdsa_key() {
+        let key = EcdsaPrivateKey::generate(EcGroup::challenge_59());
+        let message = b"hi mom";
+        let signature = key.sign(message);
+        assert!(forge_ecdsa_key(&key.public_key(), b"hi dad", &signature).is_none());
+
+        let forged = forge_ecdsa_key(&key.public_key(), message, &signature).unwrap();
+        assert_eq!(forged.group().curve(), key.group().curve());
+        assert_ne!(forged.public_key(), key.public_key());
+        assert!(forged.public_key().verify(message, &signature));
+        assert!(!forged.public_key().verify(b"hi dad", &signature));
+
+        // the forged key is a proper key pair, which signs new messages
+        let other = forged.sign(b"hi dad");
+        assert!(forged.public_key().verify(b"hi dad", &other));
+    }
+}
@@ -0,0 +1,135 @@
+use getset::Getters;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use openssl::sha::sha1;
+
+use crate::{invmod, EcGroup, EcPoint};
+
+#[derive(Getters, Clone, Debug, Eq, PartialEq, Hash)]
+#[getset(get = "pub")]
+pub struct EcdsaSignature {
+    r: BigUint,
+    s: BigUint,
+}
+
+impl EcdsaSignature {
+    pub fn new(r: BigUint, s: BigUint) -> Self {
+        Self { r, s }
+    }
+}
+
+#[derive(Getters, Clone, Debug, Eq, PartialEq)]
+#[getset(get = "pub")]
+pub struct EcdsaPublicKey {
+    group: EcGroup,
+    point: EcPoint,
+}
+
+#[derive(Getters, Clone, Debug, Eq, PartialEq)]
+#[getset(get = "pub")]
+pub struct EcdsaPrivateKey {
+    group: EcGroup,
+    d: BigUint,
+    point: EcPoint,
+}
+
+/// calculates the SHA-1 hash of `message`, interpreted as integer and
+/// truncated to the bit length of `order`
+pub fn ecdsa_hash(message: &[u8], order: &BigUint) -> BigUint {
+    let hash = BigUint::from_bytes_be(&sha1(message));
+    hash >> 160u64.saturating_sub(order.bits())
+}
+
+/// the point `u1 * G + u2 * Q` which the verifier compares to `r`, where
+/// `u1 = H(m) / s` and `u2 = r / s`
+pub(crate) fn ecdsa_verification_point(
+    public_key: &EcdsaPublicKey,
+    message: &[u8],
+    signature: &EcdsaSignature,
+) -> Option<EcPoint> {
+    let group = &public_key.group;
+    let n = group.order();
+    let EcdsaSignature { r, s } = signature;
+    if r.is_zero() || r >= n || s.is_zero() || s >= n {
+        return None;
+    }
+
+    let w = invmod(s, n)?;
+    let u1 = ecdsa_hash(message, n) * &w % n;
+    let u2 = r * &w % n;
+    let curve = group.curve();
+    Some(curve.add(
+        &group.public_key(&u1),
+        &curve.scalar_mul(&public_key.point, &u2),
+    ))
+}
+
+impl EcdsaPublicKey {
+    pub fn new(group: EcGroup, point: EcPoint) -> Self {
+        Self { group, point }
+    }
+
+    /// verifies `signature` after checking that `r` and `s` are in the range
+    /// `1..n`
+    pub fn verify(&self, message: &[u8], signature: &EcdsaSignature) -> bool {
+        match ecdsa_verification_point(self, message, signature) {
+            Some(EcPoint::Affine(x, _)) => x % self.group.order() == *signature.r(),
+            _ => false,
+        }
+    }
+}
+
+impl EcdsaPrivateKey {
+    pub fn generate(group: EcGroup) -> Self {
+        let d = group.generate_private_key();
+        Self::from_d(group, d)
+    }
+
+    pub fn from_d(group: EcGroup, d: BigUint) -> Self {
+        let point = group.public_key(&d);
+        Self { group, d, point }
+    }
+
+    pub fn public_key(&self) -> EcdsaPublicKey {
+        EcdsaPublicKey::new(self.group.clone(), self.point.clone())
+    }
+
+    /// signs `message` with a random nonce
+    ///
+    /// ```rust
+    /// use cryptopals::{EcGroup, EcdsaPrivateKey};
+    /// let key = EcdsaPrivateKey::generate(EcGroup::challenge_59());
+    /// let signature = key.sign(b"hello, world");
+    /// assert!(key.public_key().verify(b"hello, world", &signature));
+    /// assert!(!key.public_key().verify(b"goodbye, world", &signature));
+    /// ```
+    pub fn sign(&self, message: &[u8]) -> EcdsaSignature {
+        loop {
+            let k = rand::thread_rng().gen_biguint_range(&BigUint::one(), self.group.order());
+            if let Some(signature) = self.sign_with_nonce(message, &k) {
+                return signature;
+            }
+        }
+    }
+
+    /// signs `message` with the nonce `k`; returns `None` if `k` is not
+    /// suitable to create a valid signature
+    pub fn sign_with_nonce(&self, message: &[u8], k: &BigUint) -> Option<EcdsaSignature> {
+        let n = self.group.order();
+
+        let r = match self.group.public_key(k) {
+            EcPoint::Affine(x, _) => x % n,
+            EcPoint::Infinity => return None,
+        };
+        if r.is_zero() {
+            return None;
+        }
+
+        let s = invmod(k, n)? * (ecdsa_hash(message, n) + &self.d * &r) % n;
+        if s.is_zero() {
+            return None;
+        }
+
+        Some(EcdsaSignature::new(r, s))
+    }
+}
@@ -0,0 +1,126 @@
+use num_bigint::BigUint;
+use num_traits::One;
+
+use crate::dh::BOT_MESSAGE;
+use crate::{crt, hmac_sha256, small_prime_factors, Bytes, EcGroup, EcPoint, SMALL_FACTOR_BOUND};
+
+/// curves `y^2 = x^3 - 95051x + b` which share `p` and `a` with the curve of
+/// challenge 59, together with their orders
+const INVALID_CURVES: [(u32, &str); 3] = [
+    (210, "233970423115425145550826547352470124412"),
+    (504, "233970423115425145544350131142039591210"),
+    (727, "233970423115425145545378039958152057148"),
+];
+
+/// authenticates `message` with HMAC-SHA256 under the shared point `key`
+pub fn ecdh_mac(group: &EcGroup, key: &EcPoint, message: &[u8]) -> Bytes {
+    hmac_sha256(&key.to_bytes(group.coordinate_size())[..], message)
+}
+
+/// a bot which completes an ECDH key exchange with any point it receives,
+/// without checking that the point is on the curve, and answers with a
+/// message and its MAC under the shared point (challenge 59)
+pub struct EcdhMacBot {
+    group: EcGroup,
+    d: BigUint,
+}
+
+impl EcdhMacBot {
+    pub fn new(group: EcGroup) -> Self {
+        let d = group.generate_private_key();
+        Self { group, d }
+    }
+
+    pub fn group(&self) -> &EcGroup {
+        &self.group
+    }
+
+    pub fn public_key(&self) -> EcPoint {
+        self.group.public_key(&self.d)
+    }
+
+    /// derives the shared point from `public_key` and returns the message and
+    /// its MAC
+    pub fn respond(&self, public_key: &EcPoint) -> (Bytes, Bytes) {
+        let shared_key = self.group.shared_secret(&self.d, public_key);
+        let message = Bytes::from_ascii(BOT_MESSAGE);
+        let mac = ecdh_mac(&self.group, &shared_key, &message[..]);
+        (message, mac)
+    }
+}
+
+/// recovers the bot's private key with an invalid-curve attack, if the small
+/// factors of the orders of the invalid curves multiply to more than the
+/// order of the generator
+///
+/// The addition formulas do not involve the coefficient `b`, so the bot
+/// happily multiplies points of other curves with the same `a`. For every
+/// small prime `r` dividing the order of such a curve, we send a point `h` of
+/// order `r` and find `d mod r` by checking the MAC of each multiple of `h`.
+/// The residues are combined with the chinese remainder theorem.
+///
+/// ```rust
+/// use cryptopals::{recover_key_by_invalid_curves, EcGroup, EcdhMacBot};
+/// let bot = EcdhMacBot::new(EcGroup::challenge_59());
+/// let d = recover_key_by_invalid_curves(&bot).unwrap();
+/// assert_eq!(bot.group().public_key(&d), bot.public_key());
+/// ```
+pub fn recover_key_by_invalid_curves(bot: &EcdhMacBot) -> Option<BigUint> {
+    let group = bot.group();
+    let q = group.order();
+
+    let mut residues = Vec::new();
+    let mut modulus = BigUint::one();
+    for (b, order) in INVALID_CURVES {
+        let curve = group.curve().with_b(BigUint::from(b));
+        let order = BigUint::parse_bytes(order.as_bytes(), 10).unwrap();
+        for r in small_prime_factors(&order, SMALL_FACTOR_BOUND) {
+            if modulus > *q {
+                break;
+            }
+            // the curves may share factors, which teach us nothing new
+            if residues.iter().any(|(_, m)| *m == BigUint::from(r)) {
+                continue;
+            }
+
+            let h = curve.random_point_of_order(&order, r);
+            let (message, mac) = bot.respond(&h);
+
+            let mut shared_key = EcPoint::Infinity;
+            for residue in 0..r {
+                if ecdh_mac(group, &shared_key, &message[..]) == mac {
+                    residues.push((BigUint::from(residue), BigUint::from(r)));
+                    modulus *= r;
+                    break;
+                }
+                shared_key = curve.add(&shared_key, &h);
+            }
+        }
+    }
+
+    let d = crt(&residues).expect("distinct primes are coprime");
+    (modulus > *q).then_some(d)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use crate::{small_prime_factors, EcGroup, EcPoint, SMALL_FACTOR_BOUND};
+
+    use super::INVALID_CURVES;
+
+    #[test]
+    fn test_invalid_curves() {
+        let group = EcGroup::challenge_59();
+        for (b, order) in INVALID_CURVES {
+            let curve = group.curve().with_b(BigUint::from(b));
+            let order = BigUint::parse_bytes(order.as_bytes(), 10).unwrap();
+            let point = curve.random_point();
+            assert!(curve.contains(&point));
+            assert!(!group.curve().contains(&point));
+            assert_eq!(curve.scalar_mul(&point, &order), EcPoint::Infinity);
+            assert!(!small_prime_factors(&order, SMALL_FACTOR_BOUND).is_empty());
+        }
+    }
+}
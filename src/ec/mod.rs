@@ -0,0 +1,333 @@
+use getset::Getters;
+use lazy_static::lazy_static;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+
+use crate::{invmod, low_bits, sqrt_mod, Bytes, KangarooGroup};
+
+mod invalid_curve;
+pub use invalid_curve::*;
+
+mod montgomery;
+pub use montgomery::*;
+
+mod twist_attack;
+pub use twist_attack::*;
+
+mod ecdsa;
+pub use ecdsa::*;
+
+mod duplicate_signature;
+pub use duplicate_signature::*;
+
+mod biased_nonce;
+pub use biased_nonce::*;
+
+lazy_static! {
+    static ref CHALLENGE_59_P: BigUint =
+        BigUint::parse_bytes(b"233970423115425145524320034830162017933", 10).unwrap();
+    static ref CHALLENGE_59_GX: BigUint = BigUint::from(182u32);
+    static ref CHALLENGE_59_GY: BigUint =
+        BigUint::parse_bytes(b"85518893674295321206118380980485522083", 10).unwrap();
+    static ref CHALLENGE_59_Q: BigUint =
+        BigUint::parse_bytes(b"29246302889428143187362802287225875743", 10).unwrap();
+}
+
+/// the coefficient `a` of the curve of challenge 59, which is `-95051`
+fn challenge_59_a() -> BigUint {
+    &*CHALLENGE_59_P - 95051u32
+}
+
+/// a point on an elliptic curve in affine coordinates, or the point at
+/// infinity
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum EcPoint {
+    Infinity,
+    Affine(BigUint, BigUint),
+}
+
+impl EcPoint {
+    /// the encoding of the point which is used as key material: the
+    /// coordinates as big endian integers, each padded to `size` bytes
+    pub fn to_bytes(&self, size: usize) -> Bytes {
+        match self {
+            EcPoint::Infinity => Bytes::from(vec![0; 2 * size]),
+            EcPoint::Affine(x, y) => {
+                let pad = |n: &BigUint| {
+                    let bytes = n.to_bytes_be();
+                    [vec![0; size.saturating_sub(bytes.len())], bytes].concat()
+                };
+                Bytes::from([pad(x), pad(y)].concat())
+            }
+        }
+    }
+}
+
+/// an elliptic curve in short Weierstrass form `y^2 = x^3 + ax + b` over
+/// the prime field of order `p`
+#[derive(Getters, Clone, Debug, Eq, PartialEq)]
+#[getset(get = "pub")]
+pub struct WeierstrassCurve {
+    p: BigUint,
+    a: BigUint,
+    b: BigUint,
+}
+
+impl WeierstrassCurve {
+    pub fn new(p: BigUint, a: BigUint, b: BigUint) -> Self {
+        Self { p, a, b }
+    }
+
+    /// the curve of challenge 59
+    pub fn challenge_59() -> Self {
+        Self::new(
+            CHALLENGE_59_P.clone(),
+            challenge_59_a(),
+            BigUint::from(11279326u32),
+        )
+    }
+
+    /// the same curve with a different coefficient `b`; the addition formulas
+    /// do not depend on `b`, so points of both curves are added the same way
+    pub fn with_b(&self, b: BigUint) -> Self {
+        Self::new(self.p.clone(), self.a.clone(), b)
+    }
+
+    /// the right hand side of the curve equation, `x^3 + ax + b`
+    fn rhs(&self, x: &BigUint) -> BigUint {
+        (x * x * x + &self.a * x + &self.b) % &self.p
+    }
+
+    pub fn contains(&self, point: &EcPoint) -> bool {
+        match point {
+            EcPoint::Infinity => true,
+            EcPoint::Affine(x, y) => *x < self.p && *y < self.p && y * y % &self.p == self.rhs(x),
+        }
+    }
+
+    /// finds a random point on the curve
+    pub fn random_point(&self) -> EcPoint {
+        loop {
+            let x = rand::thread_rng().gen_biguint_below(&self.p);
+            if let Some(y) = sqrt_mod(&self.rhs(&x), &self.p) {
+                return EcPoint::Affine(x, y);
+            }
+        }
+    }
+
+    /// finds a random point of prime order `r` on the curve, whose order is
+    /// `curve_order`
+    ///
+    /// The subgroup of order `r^k` need not be cyclic, so we multiply a random
+    /// point by the part of the order coprime to `r`, and then by `r` until
+    /// the next multiple would be the identity.
+    pub fn random_point_of_order(&self, curve_order: &BigUint, r: u64) -> EcPoint {
+        let mut cofactor = curve_order.clone();
+        while (&cofactor % r).is_zero() {
+            cofactor /= r;
+        }
+        let r = BigUint::from(r);
+        loop {
+            let mut point = self.scalar_mul(&self.random_point(), &cofactor);
+            if point == EcPoint::Infinity {
+                continue;
+            }
+            loop {
+                let next = self.scalar_mul(&point, &r);
+                if next == EcPoint::Infinity {
+                    return point;
+                }
+                point = next;
+            }
+        }
+    }
+
+    fn sub(&self, lhs: &BigUint, rhs: &BigUint) -> BigUint {
+        (lhs + &self.p - rhs % &self.p) % &self.p
+    }
+
+    pub fn negate(&self, point: &EcPoint) -> EcPoint {
+        match point {
+            EcPoint::Infinity => EcPoint::Infinity,
+            EcPoint::Affine(x, y) => EcPoint::Affine(x.clone(), self.sub(&BigUint::zero(), y)),
+        }
+    }
+
+    /// adds two points
+    ///
+    /// ```rust
+    /// use cryptopals::{EcPoint, WeierstrassCurve};
+    /// use num_bigint::BigUint;
+    /// let curve = WeierstrassCurve::new(BigUint::from(97u32), BigUint::from(2u32), BigUint::from(3u32));
+    /// let point = EcPoint::Affine(BigUint::from(3u32), BigUint::from(6u32));
+    /// let sum = curve.add(&point, &point);
+    /// assert_eq!(sum, EcPoint::Affine(BigUint::from(80u32), BigUint::from(10u32)));
+    /// assert_eq!(curve.add(&sum, &curve.negate(&point)), point);
+    /// ```
+    pub fn add(&self, lhs: &EcPoint, rhs: &EcPoint) -> EcPoint {
+        let p = &self.p;
+        let (x1, y1, x2, y2) = match (lhs, rhs) {
+            (EcPoint::Infinity, _) => return rhs.clone(),
+            (_, EcPoint::Infinity) => return lhs.clone(),
+            (EcPoint::Affine(x1, y1), EcPoint::Affine(x2, y2)) => (x1, y1, x2, y2),
+        };
+        let slope = if x1 == x2 {
+            // the points are either equal or each other's negation
+            if y1 != y2 || y1.is_zero() {
+                return EcPoint::Infinity;
+            }
+            let numerator = (3u32 * x1 * x1 + &self.a) % p;
+            numerator * invmod(&(2u32 * y1), p).expect("y is not zero") % p
+        } else {
+            let denominator = self.sub(x2, x1);
+            self.sub(y2, y1) * invmod(&denominator, p).expect("x1 and x2 differ") % p
+        };
+        let x3 = self.sub(&(&slope * &slope), &(x1 + x2));
+        let y3 = self.sub(&(slope * self.sub(x1, &x3)), y1);
+        EcPoint::Affine(x3, y3)
+    }
+
+    /// multiplies `point` by the scalar `k` with double-and-add
+    pub fn scalar_mul(&self, point: &EcPoint, k: &BigUint) -> EcPoint {
+        let mut result = EcPoint::Infinity;
+        for i in (0..k.bits()).rev() {
+            result = self.add(&result, &result);
+            if k.bit(i) {
+                result = self.add(&result, point);
+            }
+        }
+        result
+    }
+}
+
+/// a cyclic subgroup of an elliptic curve, generated by `generator` of prime
+/// order `order`
+#[derive(Getters, Clone, Debug, Eq, PartialEq)]
+#[getset(get = "pub")]
+pub struct EcGroup {
+    curve: WeierstrassCurve,
+    generator: EcPoint,
+    order: BigUint,
+}
+
+impl EcGroup {
+    pub fn new(curve: WeierstrassCurve, generator: EcPoint, order: BigUint) -> Self {
+        Self {
+            curve,
+            generator,
+            order,
+        }
+    }
+
+    /// the group of challenge 59
+    pub fn challenge_59() -> Self {
+        Self::new(
+            WeierstrassCurve::challenge_59(),
+            EcPoint::Affine(CHALLENGE_59_GX.clone(), CHALLENGE_59_GY.clone()),
+            CHALLENGE_59_Q.clone(),
+        )
+    }
+
+    /// generates a random private key in the range `1..order`
+    pub fn generate_private_key(&self) -> BigUint {
+        rand::thread_rng().gen_biguint_range(&BigUint::one(), &self.order)
+    }
+
+    /// calculates the public key `d * G`
+    pub fn public_key(&self, d: &BigUint) -> EcPoint {
+        self.curve.scalar_mul(&self.generator, d)
+    }
+
+    /// the shared secret of ECDH, `d * Q` for the private key `d` and the
+    /// other party's public key `Q`
+    ///
+    /// ```rust
+    /// use cryptopals::EcGroup;
+    /// let group = EcGroup::challenge_59();
+    /// let (alice, bob) = (group.generate_private_key(), group.generate_private_key());
+    /// assert_eq!(
+    ///     group.shared_secret(&alice, &group.public_key(&bob)),
+    ///     group.shared_secret(&bob, &group.public_key(&alice))
+    /// );
+    /// ```
+    pub fn shared_secret(&self, d: &BigUint, public_key: &EcPoint) -> EcPoint {
+        self.curve.scalar_mul(public_key, d)
+    }
+
+    /// the size of a coordinate in bytes
+    pub fn coordinate_size(&self) -> usize {
+        self.curve.p.bits().div_ceil(8) as usize
+    }
+}
+
+impl KangarooGroup for EcGroup {
+    type Element = EcPoint;
+
+    fn combine(&self, lhs: &EcPoint, rhs: &EcPoint) -> EcPoint {
+        self.curve.add(lhs, rhs)
+    }
+
+    fn generator_power(&self, exponent: &BigUint) -> EcPoint {
+        self.public_key(exponent)
+    }
+
+    fn jump_selector(&self, element: &EcPoint) -> u64 {
+        match element {
+            EcPoint::Infinity => 0,
+            EcPoint::Affine(x, _) => low_bits(x),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use crate::{EcGroup, EcPoint, WeierstrassCurve};
+
+    /// `y^2 = x^3 + 2x + 3` over `GF(97)`, which has the 2-torsion point `(96, 0)`
+    fn small_curve() -> WeierstrassCurve {
+        WeierstrassCurve::new(97u32.into(), 2u32.into(), 3u32.into())
+    }
+
+    #[test]
+    fn test_challenge_59_group() {
+        let group = EcGroup::challenge_59();
+        let curve = group.curve();
+        assert!(curve.contains(group.generator()));
+        assert_eq!(
+            curve.scalar_mul(group.generator(), group.order()),
+            EcPoint::Infinity
+        );
+
+        let point = curve.random_point();
+        assert!(curve.contains(&point));
+        let sum = curve.add(&point, group.generator());
+        assert!(curve.contains(&sum));
+        assert_eq!(curve.add(&sum, &curve.negate(group.generator())), point);
+        assert_eq!(
+            curve.scalar_mul(&point, &BigUint::from(3u32)),
+            curve.add(&point, &curve.add(&point, &point))
+        );
+    }
+
+    #[test]
+    fn test_add_negation() {
+        let curve = small_curve();
+        let point = EcPoint::Affine(3u32.into(), 6u32.into());
+        let negated = curve.negate(&point);
+        assert_eq!(negated, EcPoint::Affine(3u32.into(), 91u32.into()));
+        assert_eq!(curve.add(&point, &negated), EcPoint::Infinity);
+        assert_eq!(curve.add(&negated, &point), EcPoint::Infinity);
+    }
+
+    #[test]
+    fn test_double_2_torsion_point() {
+        let curve = small_curve();
+        let point = EcPoint::Affine(96u32.into(), BigUint::ZERO);
+        assert!(curve.contains(&point));
+        assert_eq!(curve.negate(&point), point);
+        assert_eq!(curve.add(&point, &point), EcPoint::Infinity);
+        assert_eq!(curve.scalar_mul(&point, &BigUint::from(3u32)), point);
+    }
+}
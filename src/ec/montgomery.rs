@@ -0,0 +1,248 @@
+use getset::Getters;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+
+use super::{CHALLENGE_59_P, CHALLENGE_59_Q};
+use crate::{invmod, sqrt_mod, EcGroup, EcPoint, WeierstrassCurve};
+
+/// an elliptic curve in Montgomery form `By^2 = x^3 + Ax^2 + x` over the
+/// prime field of order `p`; we call the coordinates `u` and `v`
+#[derive(Getters, Clone, Debug, Eq, PartialEq)]
+#[getset(get = "pub")]
+pub struct MontgomeryCurve {
+    p: BigUint,
+    a: BigUint,
+    b: BigUint,
+}
+
+impl MontgomeryCurve {
+    pub fn new(p: BigUint, a: BigUint, b: BigUint) -> Self {
+        Self { p, a, b }
+    }
+
+    /// the curve of challenge 60, which is isomorphic to the curve of
+    /// challenge 59
+    pub fn challenge_60() -> Self {
+        Self::new(
+            CHALLENGE_59_P.clone(),
+            BigUint::from(534u32),
+            BigUint::one(),
+        )
+    }
+
+    /// the quadratic twist of the curve, `nBv^2 = u^3 + Au^2 + u` for a
+    /// quadratic non-residue `n`
+    ///
+    /// Every `u` is the coordinate of a point on either the curve or its
+    /// twist, and the ladder does not depend on `B`, so it computes on both.
+    pub fn twist(&self) -> Self {
+        let mut n = BigUint::from(2u32);
+        while sqrt_mod(&n, &self.p).is_some() {
+            n += 1u32;
+        }
+        Self::new(self.p.clone(), self.a.clone(), &self.b * n % &self.p)
+    }
+
+    fn inverse(&self, n: &BigUint) -> BigUint {
+        invmod(n, &self.p).expect("n is not zero")
+    }
+
+    /// `A / 3`, the shift between `u` and the Weierstrass `x`
+    fn shift(&self) -> BigUint {
+        &self.a * self.inverse(&BigUint::from(3u32)) % &self.p
+    }
+
+    /// the isomorphic curve in Weierstrass form, with
+    /// `a = (3 - A^2) / 3B^2` and `b = (2A^3 - 9A) / 27B^3`
+    pub fn to_weierstrass(&self) -> WeierstrassCurve {
+        let p = &self.p;
+        let (a, b) = (&self.a, &self.b);
+        let a_squared = a * a % p;
+        let weierstrass_a =
+            (BigUint::from(3u32) + p - &a_squared) % p * self.inverse(&(3u32 * b * b % p)) % p;
+        let weierstrass_b = (2u32 * &a_squared * a + 9u32 * (p - a)) % p
+            * self.inverse(&(27u32 * b * b * b % p))
+            % p;
+        WeierstrassCurve::new(p.clone(), weierstrass_a, weierstrass_b)
+    }
+
+    /// maps `(u, v)` to the point `(u / B + A / 3B, v / B)` of
+    /// [`to_weierstrass`](Self::to_weierstrass)
+    pub fn to_weierstrass_point(&self, u: &BigUint, v: &BigUint) -> EcPoint {
+        let p = &self.p;
+        let b_inverse = self.inverse(&self.b);
+        EcPoint::Affine((u + self.shift()) * &b_inverse % p, v * b_inverse % p)
+    }
+
+    /// the `u` coordinate of a point of [`to_weierstrass`](Self::to_weierstrass),
+    /// `u = Bx - A / 3`, where the point at infinity maps to 0 like in the
+    /// ladder
+    pub fn u_of_weierstrass_point(&self, point: &EcPoint) -> BigUint {
+        match point {
+            EcPoint::Infinity => BigUint::zero(),
+            EcPoint::Affine(x, _) => (&self.b * x + &self.p - self.shift()) % &self.p,
+        }
+    }
+
+    /// computes the `u` coordinate of `k` times the point with coordinate
+    /// `u` with the Montgomery ladder, which never needs `v`
+    ///
+    /// The point at infinity has no `u` coordinate and is returned as 0. The
+    /// ladder takes a step for every bit of `p`, or of `k` if that is longer,
+    /// so all scalars below `p` take the same time.
+    ///
+    /// ```rust
+    /// use cryptopals::{EcGroup, MontgomeryCurve};
+    /// use num_bigint::BigUint;
+    /// let curve = MontgomeryCurve::challenge_60();
+    /// let group = EcGroup::challenge_59();
+    /// let k = BigUint::from(123456789u32);
+    /// let u = curve.ladder(&BigUint::from(4u32), &k);
+    /// assert_eq!(u, curve.u_of_weierstrass_point(&group.public_key(&k)));
+    /// ```
+    pub fn ladder(&self, u: &BigUint, k: &BigUint) -> BigUint {
+        let p = &self.p;
+        let sub = |lhs: &BigUint, rhs: &BigUint| (lhs + p - rhs % p) % p;
+
+        let (mut u2, mut w2) = (BigUint::one(), BigUint::zero());
+        let (mut u3, mut w3) = (u % p, BigUint::one());
+        for i in (0..k.bits().max(p.bits())).rev() {
+            let bit = k.bit(i);
+            if bit {
+                std::mem::swap(&mut u2, &mut u3);
+                std::mem::swap(&mut w2, &mut w3);
+            }
+            let difference = sub(&(&u2 * &u3), &(&w2 * &w3));
+            let sum = sub(&(&u2 * &w3), &(&w2 * &u3));
+            (u3, w3) = (&difference * &difference % p, u * &sum * &sum % p);
+            let squares = sub(&(&u2 * &u2), &(&w2 * &w2));
+            let mixed = (&u2 * &u2 + &self.a * &u2 * &w2 + &w2 * &w2) % p;
+            (u2, w2) = (&squares * &squares % p, 4u32 * &u2 * &w2 * mixed % p);
+            if bit {
+                std::mem::swap(&mut u2, &mut u3);
+                std::mem::swap(&mut w2, &mut w3);
+            }
+        }
+        u2 * invmod(&w2, p).unwrap_or_default() % p
+    }
+}
+
+/// a cyclic subgroup of a Montgomery curve, where points are represented by
+/// their `u` coordinate only
+#[derive(Getters, Clone, Debug, Eq, PartialEq)]
+#[getset(get = "pub")]
+pub struct MontgomeryGroup {
+    curve: MontgomeryCurve,
+    base: BigUint,
+    order: BigUint,
+}
+
+impl MontgomeryGroup {
+    pub fn new(curve: MontgomeryCurve, base: BigUint, order: BigUint) -> Self {
+        Self { curve, base, order }
+    }
+
+    /// the group of challenge 60
+    pub fn challenge_60() -> Self {
+        Self::new(
+            MontgomeryCurve::challenge_60(),
+            BigUint::from(4u32),
+            CHALLENGE_59_Q.clone(),
+        )
+    }
+
+    /// generates a random private key in the range `1..order`
+    pub fn generate_private_key(&self) -> BigUint {
+        rand::thread_rng().gen_biguint_range(&BigUint::one(), &self.order)
+    }
+
+    /// calculates the `u` coordinate of the public key `d * G`
+    pub fn public_key(&self, d: &BigUint) -> BigUint {
+        self.curve.ladder(&self.base, d)
+    }
+
+    /// the shared secret of ECDH, the `u` coordinate of `d * Q`
+    pub fn shared_secret(&self, d: &BigUint, public_key: &BigUint) -> BigUint {
+        self.curve.ladder(public_key, d)
+    }
+
+    /// the isomorphic group on the Weierstrass form of the curve
+    ///
+    /// The base point is only known by its `u` coordinate, so this picks one
+    /// of the two points with that coordinate. Their logarithms only differ
+    /// in sign, which the ladder cannot tell apart anyway.
+    pub fn to_weierstrass(&self) -> EcGroup {
+        EcGroup::new(
+            self.curve.to_weierstrass(),
+            self.lift(&self.base)
+                .expect("the base point is on the curve"),
+            self.order.clone(),
+        )
+    }
+
+    /// lifts `u` to one of the two points with this coordinate on the
+    /// Weierstrass form of the curve, if there is one
+    pub fn lift(&self, u: &BigUint) -> Option<EcPoint> {
+        let curve = &self.curve;
+        let p = curve.p();
+        let rhs = (u * u * u + curve.a() * u * u + u) % p;
+        let v_squared = rhs * invmod(curve.b(), p)? % p;
+        let v = sqrt_mod(&v_squared, p)?;
+        Some(curve.to_weierstrass_point(u, &v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use crate::{EcGroup, MontgomeryCurve, MontgomeryGroup};
+
+    #[test]
+    fn test_weierstrass_form() {
+        let curve = MontgomeryCurve::challenge_60();
+        let weierstrass = EcGroup::challenge_59();
+        assert_eq!(curve.to_weierstrass(), *weierstrass.curve());
+
+        let group = MontgomeryGroup::challenge_60();
+        let converted = group.to_weierstrass();
+        let generator = weierstrass.generator();
+        assert!(
+            *converted.generator() == *generator
+                || *converted.generator() == weierstrass.curve().negate(generator)
+        );
+        assert_eq!(curve.u_of_weierstrass_point(generator), *group.base());
+        assert_eq!(group.public_key(group.order()), BigUint::ZERO);
+    }
+
+    #[test]
+    fn test_twist() {
+        let curve = MontgomeryCurve::challenge_60();
+        let twist = curve.twist();
+        let weierstrass = twist.to_weierstrass();
+        let point = weierstrass.random_point();
+        let u = twist.u_of_weierstrass_point(&point);
+
+        // the coordinate belongs to the twist but not to the curve
+        let group = MontgomeryGroup::challenge_60();
+        assert!(group.lift(&u).is_none());
+
+        let k = BigUint::from(987654321u32);
+        assert_eq!(
+            curve.ladder(&u, &k),
+            twist.u_of_weierstrass_point(&weierstrass.scalar_mul(&point, &k))
+        );
+    }
+
+    #[test]
+    fn test_ladder_large_scalar() {
+        let curve = MontgomeryCurve::challenge_60();
+        let group = EcGroup::challenge_59();
+        let k = curve.p() * curve.p() + 12345u32;
+        assert!(k.bits() > curve.p().bits());
+        assert_eq!(
+            curve.ladder(&BigUint::from(4u32), &k),
+            curve.u_of_weierstrass_point(&group.public_key(&k))
+        );
+    }
+}
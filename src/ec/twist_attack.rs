@@ -0,0 +1,253 @@
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use crate::dh::BOT_MESSAGE;
+use crate::{
+    crt, hmac_sha256, kangaroo_any, small_prime_factors, Bytes, EcGroup, EcPoint,
+    KangarooParameters, MontgomeryGroup,
+};
+
+/// the order of the twist of the curve of challenge 60, which is
+/// `2 * p + 2` minus the order of the curve
+const TWIST_ORDER: &str = "233970423115425145549737651362517029924";
+
+/// the bound for the prime factors of the twist order which the attack uses;
+/// the largest one below it is 2323367
+pub const TWIST_FACTOR_BOUND: u64 = 1 << 22;
+
+/// authenticates `message` with HMAC-SHA256 under the shared coordinate `u`
+pub fn ladder_mac(group: &MontgomeryGroup, u: &BigUint, message: &[u8]) -> Bytes {
+    let size = group.curve().p().bits().div_ceil(8) as usize;
+    let bytes = u.to_bytes_be();
+    let key = [vec![0; size.saturating_sub(bytes.len())], bytes].concat();
+    hmac_sha256(&key, message)
+}
+
+/// a bot which completes an x-only ECDH key exchange with any coordinate it
+/// receives, and answers with a message and its MAC under the shared
+/// coordinate (challenge 60)
+///
+/// The bot cannot be fooled with invalid curves, because the ladder does not
+/// depend on `B`. But it does not check that the coordinate belongs to its
+/// curve rather than to the twist.
+pub struct MontgomeryMacBot {
+    group: MontgomeryGroup,
+    d: BigUint,
+}
+
+impl MontgomeryMacBot {
+    pub fn new(group: MontgomeryGroup) -> Self {
+        let d = group.generate_private_key();
+        Self { group, d }
+    }
+
+    pub fn group(&self) -> &MontgomeryGroup {
+        &self.group
+    }
+
+    pub fn public_key(&self) -> BigUint {
+        self.group.public_key(&self.d)
+    }
+
+    /// derives the shared coordinate from `public_key` and returns the
+    /// message and its MAC
+    pub fn respond(&self, public_key: &BigUint) -> (Bytes, Bytes) {
+        let shared_key = self.group.shared_secret(&self.d, public_key);
+        let message = Bytes::from_ascii(BOT_MESSAGE);
+        let mac = ladder_mac(&self.group, &shared_key, &message[..]);
+        (message, mac)
+    }
+}
+
+/// learns the bot's private key up to sign modulo the odd primes below
+/// `bound` which divide the order of the twist
+///
+/// For every prime `r`, we send the coordinate of a twist point `h` of order
+/// `r`. The points `i * h` and `-i * h` share their coordinate, so the MAC
+/// only reveals `d mod r` up to sign, and we only need to try `i <= r / 2`.
+/// The prime 2 is skipped, because the point of order 2 has coordinate 0,
+/// which the ladder also returns for the point at infinity.
+///
+/// To fix the relative signs of the residues, we send the sum of all the
+/// points, and try every combination of signs against its MAC.
+///
+/// Returns `n` and `m`, where `d = n mod m` or `d = -n mod m`, and `m` is
+/// the product of the primes.
+pub fn twist_residues(bot: &MontgomeryMacBot, bound: u64) -> (BigUint, BigUint) {
+    let group = bot.group();
+    let twist = group.curve().twist();
+    let curve = twist.to_weierstrass();
+    let twist_order = BigUint::parse_bytes(TWIST_ORDER.as_bytes(), 10).unwrap();
+
+    let mut residues = Vec::new();
+    let mut sum = EcPoint::Infinity;
+    for r in small_prime_factors(&twist_order, bound) {
+        if r == 2 {
+            continue;
+        }
+        let h = curve.random_point_of_order(&twist_order, r);
+        let (message, mac) = bot.respond(&twist.u_of_weierstrass_point(&h));
+
+        let mut shared_key = EcPoint::Infinity;
+        for residue in 0..=r / 2 {
+            let u = twist.u_of_weierstrass_point(&shared_key);
+            if ladder_mac(group, &u, &message[..]) == mac {
+                residues.push((BigUint::from(residue), BigUint::from(r)));
+                break;
+            }
+            shared_key = curve.add(&shared_key, &h);
+        }
+        sum = curve.add(&sum, &h);
+    }
+
+    let modulus: BigUint = residues.iter().map(|(_, m)| m).product();
+    if residues.is_empty() {
+        return (BigUint::zero(), modulus);
+    }
+    let (message, mac) = bot.respond(&twist.u_of_weierstrass_point(&sum));
+    // flipping all signs gives the same coordinate, so the first residue
+    // keeps its sign
+    for signs in 0..1u64 << (residues.len() - 1) {
+        let signed: Vec<_> = residues
+            .iter()
+            .enumerate()
+            .map(
+                |(i, (residue, m))| match i > 0 && signs >> (i - 1) & 1 == 1 {
+                    true => ((m - residue) % m, m.clone()),
+                    false => (residue.clone(), m.clone()),
+                },
+            )
+            .collect();
+        let n = crt(&signed).expect("distinct primes are coprime");
+        let u = twist.u_of_weierstrass_point(&curve.scalar_mul(&sum, &n));
+        if ladder_mac(group, &u, &message[..]) == mac {
+            return (n, modulus);
+        }
+    }
+    unreachable!("one combination of signs matches the bot's key")
+}
+
+/// recovers the bot's private key by combining the twist attack with the
+/// kangaroo algorithm
+///
+/// The twist attack gives us `d = ±n mod r`. We move the public key to the
+/// Weierstrass form of the curve, where it is one of the two points with its
+/// coordinate, so its logarithm is either `d` or `q - d`. That leaves four
+/// candidates `e` for its logarithm modulo `r`, and for each we search
+/// `e + m * r` with the kangaroo algorithm as in challenge 58, all sharing a
+/// single trap. Returns `None` if the kangaroos fail repeatedly.
+///
+/// Since the bot only uses coordinates, the private keys `d` and `q - d` are
+/// indistinguishable, and this may return either of them. The interval left
+/// for the kangaroos has about `2^40` elements, so this takes a few minutes
+/// in release mode.
+pub fn recover_key_by_twist_attack(bot: &MontgomeryMacBot) -> Option<BigUint> {
+    let group = bot.group();
+    let q = group.order();
+    let (n, r) = twist_residues(bot, TWIST_FACTOR_BOUND);
+    if r > *q {
+        return Some(n);
+    }
+
+    let upper = (q - 1u32) / &r;
+    recover_key_from_residue(group, &bot.public_key(), &n, &r, &upper)
+}
+
+/// the kangaroo stage of [`recover_key_by_twist_attack`], which looks for a
+/// private key `±n + m * r` with `m` in `[0, upper]`
+fn recover_key_from_residue(
+    group: &MontgomeryGroup,
+    public_key: &BigUint,
+    n: &BigUint,
+    r: &BigUint,
+    upper: &BigUint,
+) -> Option<BigUint> {
+    let q = group.order();
+    let weierstrass = group.to_weierstrass();
+    let curve = weierstrass.curve();
+    let public_key = group.lift(public_key)?;
+    let confined = EcGroup::new(curve.clone(), weierstrass.public_key(r), q.clone());
+
+    let residues = [n + r, r - n, q + n, q + r - n].map(|e| e % r);
+    let ys: Vec<_> = residues
+        .iter()
+        .map(|e| curve.add(&public_key, &curve.negate(&weierstrass.public_key(e))))
+        .collect();
+
+    let parameters = KangarooParameters::for_interval(upper);
+    // the kangaroos miss each other every now and then, so we retry with
+    // different jump functions
+    let (index, m) = (0..3).find_map(|i| {
+        let parameters = KangarooParameters::new(parameters.k + i);
+        kangaroo_any(&confined, &ys, &BigUint::ZERO, upper, parameters)
+    })?;
+    Some(&residues[index] + m * r)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use num_traits::Zero;
+
+    use crate::{recover_key_by_twist_attack, twist_residues, MontgomeryGroup, MontgomeryMacBot};
+
+    use super::{recover_key_from_residue, TWIST_ORDER};
+
+    #[test]
+    fn test_twist_order() {
+        let group = MontgomeryGroup::challenge_60();
+        let twist = group.curve().twist();
+        let curve = twist.to_weierstrass();
+        let point = curve.random_point();
+        let order = BigUint::parse_bytes(TWIST_ORDER.as_bytes(), 10).unwrap();
+        let u = twist.u_of_weierstrass_point(&point);
+        assert!(group.curve().ladder(&u, &order).is_zero());
+    }
+
+    #[test]
+    fn test_twist_residues() {
+        let bot = MontgomeryMacBot::new(MontgomeryGroup::challenge_60());
+        let (n, m) = twist_residues(&bot, 1 << 12);
+        assert_eq!(m, BigUint::from(11u32 * 107 * 197 * 1621));
+
+        let residue = &bot.d % &m;
+        assert!(residue == n || residue == (&m - &n) % &m);
+    }
+
+    #[test]
+    fn test_kangaroo_stage() {
+        let group = MontgomeryGroup::challenge_60();
+        let weierstrass = group.to_weierstrass();
+        let r = BigUint::from(11u32 * 107 * 197 * 1621);
+        let upper = BigUint::from(1u32 << 16);
+
+        // the kangaroos look for the logarithm of the lifted public key, which
+        // is either `d` or `q - d`, so we choose a `d` which is lifted to its
+        // own point; only one of the four candidates lies in the interval then
+        let d = (0u32..)
+            .map(|i| &r * 12345u32 + i)
+            .find(|d| group.lift(&group.public_key(d)) == Some(weierstrass.public_key(d)))
+            .unwrap();
+        let public_key = group.public_key(&d);
+        for n in [&d % &r, &r - &d % &r] {
+            let recovered = recover_key_from_residue(&group, &public_key, &n, &r, &upper);
+            assert_eq!(recovered, Some(d.clone()));
+        }
+
+        // keys outside of the interval are not found
+        let d = &r * (1u64 << 40) + 4321u32;
+        let public_key = group.public_key(&d);
+        assert_eq!(
+            recover_key_from_residue(&group, &public_key, &(&d % &r), &r, &upper),
+            None
+        );
+    }
+
+    #[test]
+    #[ignore = "takes a while; run with --release --ignored"]
+    fn test_twist_attack() {
+        let bot = MontgomeryMacBot::new(MontgomeryGroup::challenge_60());
+        let d = recover_key_by_twist_attack(&bot).unwrap();
+        assert_eq!(bot.group().public_key(&d), bot.public_key());
+    }
+}
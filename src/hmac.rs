@@ -0,0 +1,38 @@
+use openssl::sha::sha256;
+
+use crate::Bytes;
+
+const SHA256_BLOCKSIZE: usize = 64;
+
+/// calculates the HMAC of `message` as described in
+/// <https://datatracker.ietf.org/doc/html/rfc2104>
+fn hmac(hash: impl Fn(&[u8]) -> Vec<u8>, block_size: usize, key: &[u8], message: &[u8]) -> Bytes {
+    let mut key = if key.len() > block_size {
+        hash(key)
+    } else {
+        Vec::from(key)
+    };
+    key.resize(block_size, 0x00);
+
+    let inner_key: Vec<_> = key.iter().map(|b| b ^ 0x36).collect();
+    let outer_key: Vec<_> = key.iter().map(|b| b ^ 0x5c).collect();
+
+    let inner_hash = hash(&[&inner_key[..], message].concat());
+    Bytes::from(hash(&[&outer_key[..], &inner_hash[..]].concat()))
+}
+
+/// calculates HMAC-SHA256
+///
+/// ```rust
+/// use cryptopals::hmac_sha256;
+/// let mac = hmac_sha256(b"key", b"The quick brown fox jumps over the lazy dog");
+/// assert_eq!(mac.to_hex(), "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8");
+/// ```
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> Bytes {
+    hmac(
+        |data| Vec::from(sha256(data)),
+        SHA256_BLOCKSIZE,
+        key,
+        message,
+    )
+}
@@ -0,0 +1,150 @@
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+/// a cyclic group in which [`kangaroo`] can compute discrete logarithms
+pub trait KangarooGroup {
+    type Element: Clone + Eq;
+
+    /// the group operation
+    fn combine(&self, lhs: &Self::Element, rhs: &Self::Element) -> Self::Element;
+
+    /// the generator raised to `exponent`
+    fn generator_power(&self, exponent: &BigUint) -> Self::Element;
+
+    /// maps an element to an integer which selects the next jump; this only
+    /// needs to be deterministic and roughly uniform
+    fn jump_selector(&self, element: &Self::Element) -> u64;
+}
+
+/// the parameters of the pseudorandom jump function of [`kangaroo`]
+///
+/// An element `y` makes the kangaroos jump `2^(jump_selector(y) mod k)`
+/// steps, and the tame kangaroo makes `tame_jumps` jumps before it sets its
+/// trap.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct KangarooParameters {
+    pub k: u32,
+    pub tame_jumps: u64,
+}
+
+impl KangarooParameters {
+    /// uses jumps of up to `2^(k-1)` steps, and lets the tame kangaroo make
+    /// four times as many jumps as the mean jump is long
+    pub fn new(k: u32) -> Self {
+        Self {
+            k,
+            tame_jumps: 4 * Self::mean_jump(k),
+        }
+    }
+
+    /// chooses `k` so that the mean jump is about half the square root of
+    /// the interval size
+    pub fn for_interval(size: &BigUint) -> Self {
+        let target = size.sqrt() / 2u32;
+        let mut k = 1;
+        while BigUint::from(Self::mean_jump(k)) < target {
+            k += 1;
+        }
+        Self::new(k)
+    }
+
+    /// the mean length of the jumps `2^0, ..., 2^(k-1)`
+    fn mean_jump(k: u32) -> u64 {
+        ((1u64 << k) - 1) / k as u64
+    }
+}
+
+/// computes the discrete logarithm of `y` to the generator of `group`, if it
+/// lies in the interval `[a, b]`, with Pollard's kangaroo algorithm
+/// (challenge 58)
+///
+/// A tame kangaroo starts at `g^b` and makes a fixed number of jumps, where
+/// each jump is determined by the current element. Then a wild kangaroo starts
+/// at `y` and jumps with the same function. If it ever lands on an element
+/// the tame kangaroo has visited, both follow the same path from there on, so
+/// the wild kangaroo ends up in the trap at the tame kangaroo's final
+/// position. If it passes the trap without falling into it, the algorithm
+/// gives up. This takes about `sqrt(b - a)` group operations.
+pub fn kangaroo<G: KangarooGroup>(
+    group: &G,
+    y: &G::Element,
+    a: &BigUint,
+    b: &BigUint,
+    parameters: KangarooParameters,
+) -> Option<BigUint> {
+    kangaroo_any(group, std::slice::from_ref(y), a, b, parameters).map(|(_, x)| x)
+}
+
+/// computes the discrete logarithm of one of `ys`, if it lies in the interval
+/// `[a, b]`, and returns its index along with the logarithm
+///
+/// This works like [`kangaroo`], but the trap of the tame kangaroo is reused
+/// for a wild kangaroo from each of `ys` in turn, so a handful of candidates
+/// costs little more than a single one.
+pub fn kangaroo_any<G: KangarooGroup>(
+    group: &G,
+    ys: &[G::Element],
+    a: &BigUint,
+    b: &BigUint,
+    parameters: KangarooParameters,
+) -> Option<(usize, BigUint)> {
+    let jumps: Vec<(u64, G::Element)> = (0..parameters.k)
+        .map(|i| (1 << i, group.generator_power(&BigUint::from(1u64 << i))))
+        .collect();
+    let jump = |element: &G::Element| {
+        &jumps[(group.jump_selector(element) % parameters.k as u64) as usize]
+    };
+
+    let mut tame_distance = BigUint::zero();
+    let mut tame = group.generator_power(b);
+    for _ in 0..parameters.tame_jumps {
+        let (length, step) = jump(&tame);
+        tame_distance += *length;
+        tame = group.combine(&tame, step);
+    }
+
+    let limit = b - a + &tame_distance;
+    ys.iter().enumerate().find_map(|(index, y)| {
+        let mut wild_distance = BigUint::zero();
+        let mut wild = y.clone();
+        while wild_distance <= limit {
+            if wild == tame {
+                return Some((index, b + &tame_distance - wild_distance));
+            }
+            let (length, step) = jump(&wild);
+            wild_distance += *length;
+            wild = group.combine(&wild, step);
+        }
+        None
+    })
+}
+
+/// the lowest 64 bits of `n`, which is a convenient jump selector for
+/// elements represented by integers
+pub(crate) fn low_bits(n: &BigUint) -> u64 {
+    n.iter_u64_digits().next().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use crate::{kangaroo_any, DhGroup, KangarooParameters};
+
+    #[test]
+    fn test_kangaroo_any() {
+        let group = DhGroup::challenge_58();
+        let a = BigUint::from(1000u32);
+        let b = BigUint::from(1u32 << 20);
+        let parameters = KangarooParameters::for_interval(&(&b - &a));
+
+        // only the second logarithm lies in the interval
+        let x = BigUint::from(654321u32);
+        let outside = [BigUint::from(1u64 << 40), BigUint::from(1u64 << 50)];
+        let ys = [&outside[0], &x, &outside[1]].map(|x| group.public_key(x));
+        assert_eq!(kangaroo_any(&group, &ys, &a, &b, parameters), Some((1, x)));
+
+        let ys = outside.map(|x| group.public_key(&x));
+        assert_eq!(kangaroo_any(&group, &ys, &a, &b, parameters), None);
+    }
+}
@@ -18,4 +18,46 @@ mod aes;
 pub use aes::*;
 
 mod oracle;
-pub use oracle::*;
\ No newline at end of file
+pub use oracle::*;
+
+mod hmac;
+pub use hmac::*;
+
+mod dh;
+pub use dh::*;
+
+mod srp;
+pub use srp::*;
+
+mod number_theory;
+pub use number_theory::*;
+
+mod kangaroo;
+pub use kangaroo::*;
+
+mod lll;
+pub use lll::*;
+
+mod rsa;
+pub use rsa::*;
+
+mod dsa;
+pub use dsa::*;
+
+mod cbc_mac;
+pub use cbc_mac::*;
+
+mod compression_oracle;
+pub use compression_oracle::*;
+
+mod md_hash;
+pub use md_hash::*;
+
+mod md4;
+pub use md4::*;
+
+mod rc4;
+pub use rc4::*;
+
+mod ec;
+pub use ec::*;
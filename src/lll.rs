@@ -0,0 +1,139 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Signed, Zero};
+
+/// the inner product of two vectors
+fn dot(lhs: &[BigInt], rhs: &[BigInt]) -> BigInt {
+    lhs.iter()
+        .zip(rhs)
+        .fold(BigInt::zero(), |sum, (l, r)| sum + l * r)
+}
+
+/// reduces the rows of `basis`, which must be linearly independent, with the
+/// LLL algorithm for the Lovász constant `delta`, usually 3/4 or 0.99
+///
+/// ```rust
+/// use cryptopals::lll;
+/// use num_bigint::BigInt;
+/// use num_rational::BigRational;
+/// let rational = |n: i32, d: i32| BigRational::new(BigInt::from(n), BigInt::from(d));
+/// let basis = [[-2, 0, 2, 0], [1, -2, 0, 0], [-2, 0, -4, 1], [-1, 1, 1, 2]];
+/// let denominators = [1, 2, 2, 1];
+/// let basis: Vec<Vec<_>> = basis
+///     .iter()
+///     .zip(denominators)
+///     .map(|(row, d)| row.iter().map(|n| rational(*n, d)).collect())
+///     .collect();
+/// let reduced = lll(&basis, &rational(99, 100));
+/// let expected = [[1, -2, 0, 0], [-2, 0, -4, 1], [-1, 0, 2, 4], [-3, -2, 4, 0]];
+/// for (row, expected) in reduced.iter().zip(expected) {
+///     assert_eq!(*row, expected.map(|n| rational(n, 2)));
+/// }
+/// ```
+pub fn lll(basis: &[Vec<BigRational>], delta: &BigRational) -> Vec<Vec<BigRational>> {
+    let scale = basis
+        .iter()
+        .flatten()
+        .fold(BigInt::one(), |scale, x| scale.lcm(x.denom()));
+    let integral: Vec<Vec<BigInt>> = basis
+        .iter()
+        .map(|row| row.iter().map(|x| (x * &scale).to_integer()).collect())
+        .collect();
+
+    lll_integral(integral, delta)
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|x| BigRational::new(x, scale.clone()))
+                .collect()
+        })
+        .collect()
+}
+
+/// the integral LLL algorithm on the rows of `b`
+///
+/// `d[i]` is the determinant of the Gram matrix of the first `i` vectors,
+/// and `lambda[k][j] = d[j + 1] * mu[k][j]` for the Gram-Schmidt coefficients
+/// `mu`; both are always integers.
+fn lll_integral(mut b: Vec<Vec<BigInt>>, delta: &BigRational) -> Vec<Vec<BigInt>> {
+    // see Cohen, "A Course in Computational Algebraic Number Theory", algorithm 2.6.7
+    let n = b.len();
+    if n < 2 {
+        return b;
+    }
+    let mut d = vec![BigInt::zero(); n + 1];
+    d[0] = BigInt::one();
+    d[1] = dot(&b[0], &b[0]);
+    let mut lambda = vec![vec![BigInt::zero(); n]; n];
+
+    // subtracts the closest integer multiple of b[l] from b[k]
+    let reduce = |b: &mut Vec<Vec<BigInt>>,
+                  lambda: &mut Vec<Vec<BigInt>>,
+                  d: &[BigInt],
+                  k: usize,
+                  l: usize| {
+        if 2u32 * lambda[k][l].abs() <= d[l + 1] {
+            return;
+        }
+        let q = (2u32 * &lambda[k][l] + &d[l + 1]).div_floor(&(2u32 * &d[l + 1]));
+        let (head, tail) = b.split_at_mut(k);
+        for (x, y) in tail[0].iter_mut().zip(&head[l]) {
+            *x -= &q * y;
+        }
+        lambda[k][l] -= &q * &d[l + 1];
+        let (head, tail) = lambda.split_at_mut(k);
+        for (x, y) in tail[0].iter_mut().zip(&head[l]).take(l) {
+            *x -= &q * y;
+        }
+    };
+
+    let mut k = 1;
+    let mut k_max = 0;
+    while k < n {
+        if k > k_max {
+            // incremental Gram-Schmidt for the new vector b[k]
+            k_max = k;
+            for j in 0..=k {
+                let mut u = dot(&b[k], &b[j]);
+                for i in 0..j {
+                    u = (&d[i + 1] * u - &lambda[k][i] * &lambda[j][i]) / &d[i];
+                }
+                if j < k {
+                    lambda[k][j] = u;
+                } else {
+                    assert!(!u.is_zero(), "the basis vectors are linearly dependent");
+                    d[k + 1] = u;
+                }
+            }
+        }
+
+        reduce(&mut b, &mut lambda, &d, k, k - 1);
+
+        // the Lovász condition, multiplied by d[k]^2 and the denominator of
+        // delta
+        let lhs = delta.denom() * &d[k + 1] * &d[k - 1];
+        let rhs =
+            delta.numer() * &d[k] * &d[k] - delta.denom() * &lambda[k][k - 1] * &lambda[k][k - 1];
+        if lhs < rhs {
+            b.swap(k, k - 1);
+            let (head, tail) = lambda.split_at_mut(k);
+            head[k - 1][..k - 1].swap_with_slice(&mut tail[0][..k - 1]);
+            let l = lambda[k][k - 1].clone();
+            let swapped = (&d[k - 1] * &d[k + 1] + &l * &l) / &d[k];
+            for row in lambda.iter_mut().take(k_max + 1).skip(k + 1) {
+                let t = row[k].clone();
+                row[k] = (&d[k + 1] * &row[k - 1] - &l * &t) / &d[k];
+                row[k - 1] = (&swapped * t + &l * &row[k]) / &d[k + 1];
+            }
+            d[k] = swapped;
+            k = (k - 1).max(1);
+        } else {
+            for l in (0..k - 1).rev() {
+                reduce(&mut b, &mut lambda, &d, k, l);
+            }
+            k += 1;
+        }
+    }
+    b
+}
@@ -0,0 +1,359 @@
+use getset::Getters;
+use rand::random;
+
+use super::{f, g, ROUND_1_SHIFTS, ROUND_2_CONSTANT, ROUND_2_SHIFTS};
+use crate::{md4_block, md4_compress, Bytes, MD4_INITIAL_STATE};
+
+/// a condition on a single bit of one of the intermediate values
+#[derive(Copy, Clone)]
+enum Condition {
+    Zero,
+    One,
+    /// the bit equals the same bit of the value computed `n` steps before
+    Equal(usize),
+}
+
+use Condition::*;
+
+/// the sufficient conditions of Wang et al. for the first round, indexed by
+/// step, with 0-based bit positions
+const ROUND_1_CONDITIONS: [&[(u32, Condition)]; 16] = [
+    // a1
+    &[(6, Equal(1))],
+    // d1
+    &[(6, Zero), (7, Equal(1)), (10, Equal(1))],
+    // c1
+    &[(6, One), (7, One), (10, Zero), (25, Equal(1))],
+    // b1
+    &[(6, One), (7, Zero), (10, Zero), (25, Zero)],
+    // a2
+    &[(7, One), (10, One), (25, Zero), (13, Equal(1))],
+    // d2
+    &[
+        (13, Zero),
+        (18, Equal(1)),
+        (19, Equal(1)),
+        (20, Equal(1)),
+        (21, Equal(1)),
+        (25, One),
+    ],
+    // c2
+    &[
+        (12, Equal(1)),
+        (13, Zero),
+        (14, Equal(1)),
+        (18, Zero),
+        (19, Zero),
+        (20, One),
+        (21, Zero),
+    ],
+    // b2
+    &[
+        (12, One),
+        (13, One),
+        (14, Zero),
+        (16, Equal(1)),
+        (18, Zero),
+        (19, Zero),
+        (20, Zero),
+        (21, Zero),
+    ],
+    // a3
+    &[
+        (12, One),
+        (13, One),
+        (14, One),
+        (16, Zero),
+        (18, Zero),
+        (19, Zero),
+        (20, Zero),
+        (22, Equal(1)),
+        (21, One),
+        (25, Equal(1)),
+    ],
+    // d3
+    &[
+        (12, One),
+        (13, One),
+        (14, One),
+        (16, Zero),
+        (19, Zero),
+        (20, One),
+        (21, One),
+        (22, Zero),
+        (25, One),
+        (29, Equal(1)),
+    ],
+    // c3
+    &[
+        (16, One),
+        (19, Zero),
+        (20, Zero),
+        (21, Zero),
+        (22, Zero),
+        (25, Zero),
+        (29, One),
+        (31, Equal(1)),
+    ],
+    // b3
+    &[
+        (19, Zero),
+        (20, One),
+        (21, One),
+        (22, Equal(1)),
+        (25, One),
+        (29, Zero),
+        (31, Zero),
+    ],
+    // a4
+    &[
+        (22, Zero),
+        (25, Zero),
+        (26, Equal(1)),
+        (28, Equal(1)),
+        (29, One),
+        (31, Zero),
+    ],
+    // d4
+    &[
+        (22, Zero),
+        (25, Zero),
+        (26, One),
+        (28, One),
+        (29, Zero),
+        (31, One),
+    ],
+    // c4
+    &[
+        (18, Equal(1)),
+        (22, One),
+        (25, One),
+        (26, Zero),
+        (28, Zero),
+        (29, Zero),
+    ],
+    // b4
+    &[(18, Zero), (25, One), (26, One), (28, One), (29, Zero)],
+];
+
+/// the conditions of the first steps of the second round, which we try to
+/// satisfy with multi-step modifications
+///
+/// Each entry holds the conditions of one step, together with the first
+/// round step whose value is flipped to correct them. A bit of the second
+/// round value is corrected by flipping the corresponding bit of the first
+/// round value which uses the same message word; the offset accounts for the
+/// different rotations.
+const ROUND_2_CONDITIONS: [(&[(u32, Condition)], usize); 3] = [
+    // a5, corrected through a1
+    (
+        &[(18, Equal(2)), (25, One), (26, Zero), (28, One), (31, One)],
+        0,
+    ),
+    // d5, corrected through a2
+    (
+        &[
+            (18, Equal(1)),
+            (25, Equal(2)),
+            (26, Equal(2)),
+            (28, Equal(2)),
+            (31, Equal(2)),
+        ],
+        4,
+    ),
+    // c5, corrected through a3, which only works for bit 29, because the
+    // other bits would break the conditions on a3
+    (&[(29, Equal(1))], 8),
+];
+
+/// the intermediate values of the first two rounds of MD4, where `q[0..4]`
+/// holds the initial state in the order a, d, c, b and `q[4 + i]` is the
+/// value computed in step `i`
+struct Steps {
+    q: [u32; 36],
+    words: [u32; 16],
+}
+
+impl Steps {
+    fn new(words: [u32; 16]) -> Self {
+        let [a, b, c, d] = MD4_INITIAL_STATE;
+        let mut q = [0; 36];
+        q[..4].copy_from_slice(&[a, d, c, b]);
+        let mut steps = Self { q, words };
+        for step in 0..32 {
+            steps.q[4 + step] = steps.compute(step);
+        }
+        steps
+    }
+
+    /// computes the value of `step` from the previous values
+    fn compute(&self, step: usize) -> u32 {
+        let q = &self.q;
+        let (a, b, c, d) = (q[step], q[step + 3], q[step + 2], q[step + 1]);
+        if step < 16 {
+            a.wrapping_add(f(b, c, d))
+                .wrapping_add(self.words[step])
+                .rotate_left(ROUND_1_SHIFTS[step % 4])
+        } else {
+            let i = step - 16;
+            a.wrapping_add(g(b, c, d))
+                .wrapping_add(self.words[i % 4 * 4 + i / 4])
+                .wrapping_add(ROUND_2_CONSTANT)
+                .rotate_left(ROUND_2_SHIFTS[i % 4])
+        }
+    }
+
+    /// the message word which makes step `step` of the first round produce
+    /// its current value
+    fn first_round_word(&self, step: usize) -> u32 {
+        let q = &self.q;
+        let (a, b, c, d) = (q[step], q[step + 3], q[step + 2], q[step + 1]);
+        q[step + 4]
+            .rotate_right(ROUND_1_SHIFTS[step % 4])
+            .wrapping_sub(a)
+            .wrapping_sub(f(b, c, d))
+    }
+
+    /// the value of `bit` which satisfies `condition` for step `step`
+    fn required_bit(&self, step: usize, bit: u32, condition: Condition) -> u32 {
+        match condition {
+            Zero => 0,
+            One => 1,
+            Equal(n) => self.q[step + 4 - n] >> bit & 1,
+        }
+    }
+
+    fn holds(&self, step: usize, conditions: &[(u32, Condition)]) -> bool {
+        conditions.iter().all(|(bit, condition)| {
+            self.q[step + 4] >> bit & 1 == self.required_bit(step, *bit, *condition)
+        })
+    }
+
+    /// forces the conditions of the first round by adjusting each value and
+    /// deriving the message word from it
+    fn single_step_modification(&mut self) {
+        for (step, conditions) in ROUND_1_CONDITIONS.iter().enumerate() {
+            let mut value = self.compute(step);
+            for (bit, condition) in conditions.iter() {
+                let required = self.required_bit(step, *bit, *condition);
+                value = value & !(1 << bit) | required << bit;
+            }
+            self.q[step + 4] = value;
+            self.words[step] = self.first_round_word(step);
+        }
+        for step in 16..32 {
+            self.q[step + 4] = self.compute(step);
+        }
+    }
+
+    /// corrects the conditions of the first steps of the second round by
+    /// flipping a bit of a first round value, and adjusting the message
+    /// words of that step and the four following ones, so that all other
+    /// first round values stay the same
+    fn multi_step_modification(&mut self) {
+        for (i, (conditions, first_round_step)) in ROUND_2_CONDITIONS.iter().enumerate() {
+            let step = 16 + i;
+            let offset = ROUND_2_SHIFTS[i] - ROUND_1_SHIFTS[0];
+            for (bit, condition) in conditions.iter() {
+                let required = self.required_bit(step, *bit, *condition);
+                if self.q[step + 4] >> bit & 1 == required {
+                    continue;
+                }
+
+                self.q[first_round_step + 4] ^= 1 << (bit - offset);
+                for j in *first_round_step..first_round_step + 5 {
+                    self.words[j] = self.first_round_word(j);
+                }
+                for step in 16..32 {
+                    self.q[step + 4] = self.compute(step);
+                }
+            }
+        }
+    }
+
+    fn first_round_holds(&self) -> bool {
+        ROUND_1_CONDITIONS
+            .iter()
+            .enumerate()
+            .all(|(step, conditions)| self.holds(step, conditions))
+    }
+}
+
+/// applies the message differential of Wang et al.
+fn apply_differential(words: &[u32; 16]) -> [u32; 16] {
+    let mut words = *words;
+    words[1] = words[1].wrapping_add(1 << 31);
+    words[2] = words[2].wrapping_add((1 << 31) - (1 << 28));
+    words[12] = words[12].wrapping_sub(1 << 16);
+    words
+}
+
+/// a pair of single block messages with the same MD4 hash
+#[derive(Getters, Clone, Debug)]
+#[getset(get = "pub")]
+pub struct Md4Collision {
+    lhs: Bytes,
+    rhs: Bytes,
+    /// the number of candidate messages which were tried
+    attempts: usize,
+}
+
+/// finds an MD4 collision with the differential attack of Wang et al.
+/// (challenge 55)
+///
+/// Each attempt starts with a random block, forces all conditions of the
+/// first round with single-step modifications and corrects the first steps of
+/// the second round with multi-step modifications. The remaining conditions
+/// only hold by chance, so we keep trying until the block and its
+/// counterpart under the message differential actually collide.
+pub fn find_md4_collision() -> Md4Collision {
+    for attempts in 1.. {
+        let mut steps = Steps::new(random());
+        steps.single_step_modification();
+        steps.multi_step_modification();
+        debug_assert!(steps.first_round_holds());
+
+        let other = apply_differential(&steps.words);
+        let (mut lhs, mut rhs) = (MD4_INITIAL_STATE, MD4_INITIAL_STATE);
+        md4_compress(&mut lhs, &steps.words);
+        md4_compress(&mut rhs, &other);
+
+        if lhs == rhs {
+            return Md4Collision {
+                lhs: md4_block(&steps.words),
+                rhs: md4_block(&other),
+                attempts,
+            };
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::random;
+
+    use crate::{find_md4_collision, md4};
+
+    use super::Steps;
+
+    #[test]
+    fn test_modifications() {
+        for _ in 0..100 {
+            let mut steps = Steps::new(random());
+            steps.single_step_modification();
+            assert!(steps.first_round_holds());
+
+            steps.multi_step_modification();
+            assert!(steps.first_round_holds());
+            assert_eq!(Steps::new(steps.words).q, steps.q);
+        }
+    }
+
+    #[test]
+    fn test_collision() {
+        let collision = find_md4_collision();
+        assert_ne!(collision.lhs(), collision.rhs());
+        assert_eq!(md4(&collision.lhs()[..]), md4(&collision.rhs()[..]));
+    }
+}
@@ -0,0 +1,150 @@
+use crate::Bytes;
+
+mod collision;
+pub use collision::*;
+
+pub const MD4_BLOCKSIZE: usize = 64;
+
+/// the initial state of MD4
+pub const MD4_INITIAL_STATE: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+const ROUND_2_CONSTANT: u32 = 0x5a827999;
+const ROUND_3_CONSTANT: u32 = 0x6ed9eba1;
+
+/// the shift amounts of the four steps of each round
+const ROUND_1_SHIFTS: [u32; 4] = [3, 7, 11, 19];
+const ROUND_2_SHIFTS: [u32; 4] = [3, 5, 9, 13];
+const ROUND_3_SHIFTS: [u32; 4] = [3, 9, 11, 15];
+
+/// the order in which the third round processes the message words
+const ROUND_3_ORDER: [usize; 16] = [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15];
+
+fn f(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (!x & z)
+}
+
+fn g(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (x & z) | (y & z)
+}
+
+fn h(x: u32, y: u32, z: u32) -> u32 {
+    x ^ y ^ z
+}
+
+/// splits a block into its 16 little endian words
+pub fn md4_words(block: &[u8]) -> [u32; 16] {
+    assert_eq!(block.len(), MD4_BLOCKSIZE, "expected a single block");
+    let mut words = [0; 16];
+    for (word, chunk) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+    words
+}
+
+/// the inverse of [`md4_words`]
+pub fn md4_block(words: &[u32; 16]) -> Bytes {
+    Bytes::from(
+        words
+            .iter()
+            .flat_map(|w| w.to_le_bytes())
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// the MD4 compression function, which updates `state` with the 16 words of
+/// a single block
+pub fn md4_compress(state: &mut [u32; 4], words: &[u32; 16]) {
+    let [mut a, mut b, mut c, mut d] = *state;
+
+    for i in 0..16 {
+        let t = a
+            .wrapping_add(f(b, c, d))
+            .wrapping_add(words[i])
+            .rotate_left(ROUND_1_SHIFTS[i % 4]);
+        (a, b, c, d) = (d, t, b, c);
+    }
+    for i in 0..16 {
+        let t = a
+            .wrapping_add(g(b, c, d))
+            .wrapping_add(words[i % 4 * 4 + i / 4])
+            .wrapping_add(ROUND_2_CONSTANT)
+            .rotate_left(ROUND_2_SHIFTS[i % 4]);
+        (a, b, c, d) = (d, t, b, c);
+    }
+    for i in 0..16 {
+        let t = a
+            .wrapping_add(h(b, c, d))
+            .wrapping_add(words[ROUND_3_ORDER[i]])
+            .wrapping_add(ROUND_3_CONSTANT)
+            .rotate_left(ROUND_3_SHIFTS[i % 4]);
+        (a, b, c, d) = (d, t, b, c);
+    }
+
+    for (s, x) in state.iter_mut().zip([a, b, c, d]) {
+        *s = s.wrapping_add(x);
+    }
+}
+
+/// calculates the MD4 hash of `message` as described in
+/// <https://datatracker.ietf.org/doc/html/rfc1320>
+///
+/// ```rust
+/// use cryptopals::md4;
+/// assert_eq!(md4(b"abc").to_hex(), "a448017aaf21d8525fc10ae87aa6729d");
+/// ```
+pub fn md4(message: &[u8]) -> Bytes {
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    while padded.len() % MD4_BLOCKSIZE != 56 {
+        padded.push(0);
+    }
+    padded.extend((8 * message.len() as u64).to_le_bytes());
+
+    let mut state = MD4_INITIAL_STATE;
+    for block in padded.chunks_exact(MD4_BLOCKSIZE) {
+        md4_compress(&mut state, &md4_words(block));
+    }
+    Bytes::from(
+        state
+            .iter()
+            .flat_map(|w| w.to_le_bytes())
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{md4, md4_block, md4_words};
+
+    #[test]
+    fn test_md4() {
+        // the test suite of RFC 1320
+        let tests = [
+            ("", "31d6cfe0d16ae931b73c59d7e0c089c0"),
+            ("a", "bde52cb31de33e46245e05fbdbd6fb24"),
+            ("abc", "a448017aaf21d8525fc10ae87aa6729d"),
+            ("message digest", "d9130a8164549fe818874806e1c7014b"),
+            (
+                "abcdefghijklmnopqrstuvwxyz",
+                "d79e1c308aa5bbcdeea8ed63df412da9",
+            ),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "043f8582f241db351ce627e153e7f0e4",
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "e33b4ddc9c38f2199c3e7b164fcc0536",
+            ),
+        ];
+        for (message, digest) in tests {
+            assert_eq!(md4(message.as_bytes()).to_hex(), digest);
+        }
+    }
+
+    #[test]
+    fn test_words() {
+        let block: Vec<u8> = (0..64).collect();
+        assert_eq!(md4_block(&md4_words(&block))[..], block[..]);
+    }
+}
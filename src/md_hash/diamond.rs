@@ -0,0 +1,132 @@
+use std::collections::{HashMap, HashSet};
+
+use rayon::prelude::*;
+
+use crate::{find_cross_collision, random_block, Bytes, MdHash, AES_BLOCKSIZE};
+
+/// a diamond structure for the Nostradamus attack (challenge 54)
+///
+/// The structure is a binary tree of `2^k` random leaf states, where each
+/// pair of states at one level is mapped to a common state at the next level
+/// by a pair of colliding blocks. From any leaf, `k` blocks lead to the root.
+pub struct DiamondStructure {
+    /// for every level, maps each state to the block which leads to the next
+    /// level, and the state there
+    levels: Vec<HashMap<Bytes, (Bytes, Bytes)>>,
+    root: Bytes,
+}
+
+impl DiamondStructure {
+    /// builds a diamond structure with `2^k` leaves; the collisions of each
+    /// level are searched for in parallel
+    pub fn build(hash: &MdHash, k: usize) -> Self {
+        let mut leaves = HashSet::new();
+        while leaves.len() < 1 << k {
+            leaves.insert(Bytes::from(&random_block()[..hash.state_size()]));
+        }
+
+        let mut states: Vec<Bytes> = leaves.into_iter().collect();
+        let mut levels = Vec::with_capacity(k);
+        while states.len() > 1 {
+            let collisions: Vec<_> = states
+                .par_chunks_exact(2)
+                .map(|pair| {
+                    let (lhs, rhs, next) = find_cross_collision(hash, &pair[0], &pair[1]);
+                    ((pair[0].clone(), lhs), (pair[1].clone(), rhs), next)
+                })
+                .collect();
+
+            let mut level = HashMap::new();
+            states = Vec::with_capacity(collisions.len());
+            for ((lhs_state, lhs), (rhs_state, rhs), next) in collisions {
+                level.insert(lhs_state, (lhs, next.clone()));
+                level.insert(rhs_state, (rhs, next.clone()));
+                states.push(next);
+            }
+            levels.push(level);
+        }
+
+        Self {
+            levels,
+            root: states.remove(0),
+        }
+    }
+
+    /// the number of levels `k`; the structure has `2^k` leaves
+    pub fn k(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// the state at the root of the structure
+    pub fn root(&self) -> &Bytes {
+        &self.root
+    }
+
+    /// the digest of every message herded from a prefix of `prefix_blocks`
+    /// blocks, which can be published before the prefix is known
+    pub fn commitment(&self, hash: &MdHash, prefix_blocks: usize) -> Bytes {
+        let length = (prefix_blocks + 1 + self.k()) * AES_BLOCKSIZE;
+        hash.compress_blocks(&self.root, &MdHash::padding(length)[..])
+    }
+
+    /// appends a linking block and the path through the structure to
+    /// `prefix`, so that the result has the digest from
+    /// [`DiamondStructure::commitment`]
+    ///
+    /// The linking block maps the state after the prefix to one of the
+    /// leaves, which takes about `2^(b-k)` compressions for a `b` bit state.
+    /// Returns `None` if the prefix does not consist of whole blocks.
+    pub fn herd(&self, hash: &MdHash, prefix: &Bytes) -> Option<Bytes> {
+        if !prefix.len().is_multiple_of(AES_BLOCKSIZE) {
+            return None;
+        }
+        let state = hash.compress_blocks(&hash.initial_state(), &prefix[..]);
+
+        let (link, mut state) = loop {
+            let link = random_block();
+            let next = hash.compress(&state, &link[..]);
+            let is_leaf = match self.levels.first() {
+                Some(leaves) => leaves.contains_key(&next),
+                None => next == self.root,
+            };
+            if is_leaf {
+                break (link, next);
+            }
+        };
+
+        let mut message = prefix.clone() + link;
+        for level in &self.levels {
+            let (block, next) = &level[&state];
+            message = message + block.clone();
+            state = next.clone();
+        }
+        Some(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bytes, DiamondStructure, MdHash};
+
+    #[test]
+    fn test_herding() {
+        let hash = MdHash::new(16);
+        let diamond = DiamondStructure::build(&hash, 5);
+        assert_eq!(diamond.k(), 5);
+        let commitment = diamond.commitment(&hash, 2);
+
+        for prefix in ["Team A beats team B 3:1", "Team B beats team A 2:0"] {
+            let prefix = Bytes::from_ascii(&format!("{prefix:32}"));
+            let message = diamond.herd(&hash, &prefix).unwrap();
+            assert!(message[..].starts_with(&prefix[..]));
+            assert_eq!(hash.hash(&message), commitment);
+        }
+    }
+
+    #[test]
+    fn test_unaligned_prefix() {
+        let hash = MdHash::new(16);
+        let diamond = DiamondStructure::build(&hash, 2);
+        assert!(diamond.herd(&hash, &Bytes::from_ascii("short")).is_none());
+    }
+}
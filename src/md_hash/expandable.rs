@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use crate::{find_cross_collision, random_block, Bytes, MdHash, AES_BLOCKSIZE};
+
+/// a Kelsey-Schneier expandable message, which can produce messages of any
+/// length from `k` to `k + 2^k - 1` blocks that all end in the same state
+/// (challenge 53)
+///
+/// Piece `i` is a collision between a single block and `2^(k-1-i) + 1`
+/// blocks, so choosing the long variant for piece `i` adds `2^(k-1-i)` blocks
+/// to the minimal length of `k` blocks.
+pub struct ExpandableMessage {
+    pieces: Vec<(Bytes, Bytes)>,
+    state: Bytes,
+}
+
+impl ExpandableMessage {
+    /// generates an expandable message with `k` pieces, starting in the
+    /// initial state of `hash`
+    pub fn generate(hash: &MdHash, k: usize) -> Self {
+        let mut pieces = Vec::with_capacity(k);
+        let mut state = hash.initial_state();
+
+        for i in 0..k {
+            let prefix = Bytes::from(vec![0; AES_BLOCKSIZE << (k - 1 - i)]);
+            let prefix_state = hash.compress_blocks(&state, &prefix[..]);
+            let (short, last, next) = find_cross_collision(hash, &state, &prefix_state);
+            pieces.push((short, prefix + last));
+            state = next;
+        }
+
+        Self { pieces, state }
+    }
+
+    /// the number of pieces `k`
+    pub fn k(&self) -> usize {
+        self.pieces.len()
+    }
+
+    /// the state after any of the messages
+    pub fn state(&self) -> &Bytes {
+        &self.state
+    }
+
+    /// the message of `blocks` blocks, if that length is in the range `k..k +
+    /// 2^k`
+    pub fn message(&self, blocks: usize) -> Option<Bytes> {
+        let k = self.k();
+        let extra = blocks.checked_sub(k).filter(|extra| *extra >> k == 0)?;
+
+        let message = self
+            .pieces
+            .iter()
+            .enumerate()
+            .map(|(i, (short, long))| {
+                if extra >> (k - 1 - i) & 1 == 0 {
+                    short
+                } else {
+                    long
+                }
+            })
+            .fold(Bytes::from(vec![]), |message, piece| {
+                message + piece.clone()
+            });
+        Some(message)
+    }
+}
+
+/// finds a second preimage for a long `message`, which has the same length
+/// and the same hash, but differs from `message`
+///
+/// We hash `message` and remember the intermediate states. Then we build an
+/// expandable message and look for a bridge block from its final state into
+/// one of the intermediate states. The second preimage consists of the
+/// expandable message with the right length, the bridge block and the rest
+/// of the original message. With `2^k` blocks, this needs about `2^(b-k)`
+/// compressions for a `b` bit state, instead of `2^b`.
+///
+/// Returns `None` if `message` is too short to hold an expandable message.
+pub fn second_preimage(hash: &MdHash, message: &Bytes) -> Option<Bytes> {
+    let blocks = message.len() / AES_BLOCKSIZE;
+    let k = blocks.checked_ilog2()? as usize;
+
+    // maps the state after block j to j, for all j where the expandable
+    // message can fill the first j blocks, so that the bridge replaces block j
+    let mut states = HashMap::new();
+    let mut state = hash.initial_state();
+    for (j, block) in message[..blocks * AES_BLOCKSIZE]
+        .chunks_exact(AES_BLOCKSIZE)
+        .enumerate()
+    {
+        state = hash.compress(&state, block);
+        if j >= k && j - k < 1 << k {
+            states.insert(state.clone(), j);
+        }
+    }
+    if states.is_empty() {
+        return None;
+    }
+
+    let expandable = ExpandableMessage::generate(hash, k);
+    loop {
+        let bridge = random_block();
+        let next = hash.compress(expandable.state(), &bridge[..]);
+        if let Some(&j) = states.get(&next) {
+            let prefix = expandable.message(j)?;
+            let suffix = Bytes::from(&message[(j + 1) * AES_BLOCKSIZE..]);
+            return Some(prefix + bridge + suffix);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{second_preimage, Bytes, ExpandableMessage, MdHash, AES_BLOCKSIZE};
+
+    #[test]
+    fn test_expandable_message() {
+        let hash = MdHash::new(16);
+        let expandable = ExpandableMessage::generate(&hash, 4);
+
+        assert!(expandable.message(3).is_none());
+        assert!(expandable.message(20).is_none());
+        for blocks in 4..20 {
+            let message = expandable.message(blocks).unwrap();
+            assert_eq!(message.len(), blocks * AES_BLOCKSIZE);
+            assert_eq!(
+                hash.compress_blocks(&hash.initial_state(), &message[..]),
+                *expandable.state()
+            );
+        }
+    }
+
+    #[test]
+    fn test_second_preimage() {
+        let hash = MdHash::new(24);
+        let message = Bytes::from(
+            (0..(AES_BLOCKSIZE << 10) + 5)
+                .map(|_| rand::random::<u8>())
+                .collect::<Vec<_>>(),
+        );
+
+        let forged = second_preimage(&hash, &message).unwrap();
+        assert_ne!(forged, message);
+        assert_eq!(forged.len(), message.len());
+        assert_eq!(hash.hash(&forged), hash.hash(&message));
+    }
+}
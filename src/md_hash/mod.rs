@@ -0,0 +1,179 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{Bytes, Key, Mode, AES, AES_BLOCKSIZE};
+
+mod multicollision;
+pub use multicollision::*;
+
+mod expandable;
+pub use expandable::*;
+
+mod diamond;
+pub use diamond::*;
+
+/// the initial state of [`MdHash`], truncated to the state size
+const INITIAL_STATE: [u8; AES_BLOCKSIZE] = [
+    0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10,
+];
+
+/// a deliberately weak Merkle-Damgård hash with a state of only a few bytes
+/// (challenges 52 to 54)
+///
+/// The compression function encrypts the message block with AES-128, using
+/// the state padded with zeros as key, and truncates the ciphertext to the
+/// state size. Messages are padded with a one bit, zeros and their length in
+/// bits, so that colliding messages of the same length also collide after
+/// padding.
+///
+/// The hash counts how often its compression function has been called.
+#[derive(Debug)]
+pub struct MdHash {
+    state_size: usize,
+    compressions: AtomicUsize,
+}
+
+impl MdHash {
+    /// creates a hash with a state of `state_bits` bits, which must be a
+    /// multiple of 8 between 8 and 128
+    pub fn new(state_bits: usize) -> Self {
+        assert!(
+            state_bits.is_multiple_of(8) && (8..=128).contains(&state_bits),
+            "unsupported state size of {state_bits} bits"
+        );
+        Self {
+            state_size: state_bits / 8,
+            compressions: AtomicUsize::new(0),
+        }
+    }
+
+    /// the size of the state and the digest in bytes
+    pub fn state_size(&self) -> usize {
+        self.state_size
+    }
+
+    pub fn initial_state(&self) -> Bytes {
+        Bytes::from(&INITIAL_STATE[..self.state_size])
+    }
+
+    /// the number of times the compression function has been called so far
+    pub fn compressions(&self) -> usize {
+        self.compressions.load(Ordering::Relaxed)
+    }
+
+    /// the compression function, which maps a state and a single block to the
+    /// next state
+    pub fn compress(&self, state: &Bytes, block: &[u8]) -> Bytes {
+        assert_eq!(block.len(), AES_BLOCKSIZE, "expected a single block");
+        self.compressions.fetch_add(1, Ordering::Relaxed);
+
+        let mut key = [0; AES_BLOCKSIZE];
+        key[..self.state_size].copy_from_slice(&state[..]);
+        let encrypted = Bytes::from(block)
+            .aes_ecb(Mode::Encrypt, &Key::AES128(key))
+            .expect("a single block can always be encrypted");
+        Bytes::from(&encrypted[..self.state_size])
+    }
+
+    /// runs the compression function over all blocks of `blocks`, starting in
+    /// `state`, without any padding
+    pub fn compress_blocks(&self, state: &Bytes, blocks: &[u8]) -> Bytes {
+        assert!(
+            blocks.len().is_multiple_of(AES_BLOCKSIZE),
+            "expected whole blocks"
+        );
+        blocks
+            .chunks_exact(AES_BLOCKSIZE)
+            .fold(state.clone(), |state, block| self.compress(&state, block))
+    }
+
+    /// the padding which is appended to a message of `length` bytes
+    pub fn padding(length: usize) -> Bytes {
+        let mut padding = vec![0x80];
+        while !(length + padding.len() + 8).is_multiple_of(AES_BLOCKSIZE) {
+            padding.push(0);
+        }
+        padding.extend((8 * length as u64).to_be_bytes());
+        padding.into()
+    }
+
+    /// hashes `message`
+    ///
+    /// ```rust
+    /// use cryptopals::{Bytes, MdHash};
+    /// let hash = MdHash::new(16);
+    /// let digest = hash.hash(&Bytes::from_ascii("hello, world"));
+    /// assert_eq!(digest.len(), 2);
+    /// assert_ne!(digest, hash.hash(&Bytes::from_ascii("goodbye, world")));
+    /// ```
+    pub fn hash(&self, message: &Bytes) -> Bytes {
+        let padded = message.clone() + Self::padding(message.len());
+        self.compress_blocks(&self.initial_state(), &padded[..])
+    }
+}
+
+/// a random message block
+pub(crate) fn random_block() -> Bytes {
+    Bytes::from(&rand::random::<[u8; AES_BLOCKSIZE]>()[..])
+}
+
+/// finds a block `lhs` for `lhs_state` and a block `rhs` for `rhs_state`
+/// which lead to the same next state, and returns them together with that
+/// state
+pub(crate) fn find_cross_collision(
+    hash: &MdHash,
+    lhs_state: &Bytes,
+    rhs_state: &Bytes,
+) -> (Bytes, Bytes, Bytes) {
+    let mut lhs_seen = HashMap::new();
+    let mut rhs_seen = HashMap::new();
+    loop {
+        let lhs = random_block();
+        let next = hash.compress(lhs_state, &lhs[..]);
+        if let Some(rhs) = rhs_seen.get(&next) {
+            return (lhs, Bytes::clone(rhs), next);
+        }
+        lhs_seen.insert(next, lhs);
+
+        let rhs = random_block();
+        let next = hash.compress(rhs_state, &rhs[..]);
+        if let Some(lhs) = lhs_seen.get(&next) {
+            return (Bytes::clone(lhs), rhs, next);
+        }
+        rhs_seen.insert(next, rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bytes, MdHash, AES_BLOCKSIZE};
+
+    #[test]
+    fn test_padding() {
+        for length in 0..40 {
+            let padding = MdHash::padding(length);
+            assert!((length + padding.len()).is_multiple_of(AES_BLOCKSIZE));
+            assert_eq!(padding[0], 0x80);
+            assert_eq!(
+                padding[padding.len() - 8..],
+                (8 * length as u64).to_be_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn test_hash() {
+        let hash = MdHash::new(24);
+        let message = Bytes::from_ascii("YELLOW SUBMARINE");
+        assert_eq!(hash.hash(&message), hash.hash(&message));
+        assert_eq!(hash.compressions(), 4);
+
+        let state = hash.compress(&hash.initial_state(), &message[..]);
+        assert_eq!(
+            hash.hash(&message),
+            hash.compress_blocks(&state, &MdHash::padding(16)[..])
+        );
+    }
+}
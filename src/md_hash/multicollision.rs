@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use crate::{random_block, Bytes, MdHash};
+
+/// finds two different blocks which both lead from `state` to the same next
+/// state with a birthday attack, and returns them together with that state
+pub fn find_block_collision(hash: &MdHash, state: &Bytes) -> (Bytes, Bytes, Bytes) {
+    let mut seen = HashMap::new();
+    loop {
+        let block = random_block();
+        let next = hash.compress(state, &block[..]);
+        match seen.insert(next.clone(), block.clone()) {
+            Some(other) if other != block => return (other, block, next),
+            _ => {}
+        }
+    }
+}
+
+/// a Joux multicollision: `n` pairs of colliding blocks, which give `2^n`
+/// messages of `n` blocks with the same hash (challenge 52)
+///
+/// Every pair costs a single birthday attack of `2^(b/2)` compressions for a
+/// `b` bit state, so the whole multicollision costs only `n * 2^(b/2)`.
+pub struct Multicollision {
+    pairs: Vec<(Bytes, Bytes)>,
+    state: Bytes,
+}
+
+impl Multicollision {
+    /// generates a multicollision of `2^n` messages, starting in the initial
+    /// state of `hash`
+    pub fn generate(hash: &MdHash, n: usize) -> Self {
+        let mut multicollision = Self {
+            pairs: Vec::new(),
+            state: hash.initial_state(),
+        };
+        for _ in 0..n {
+            multicollision.extend(hash);
+        }
+        multicollision
+    }
+
+    /// doubles the number of messages by appending another pair of colliding
+    /// blocks
+    pub fn extend(&mut self, hash: &MdHash) {
+        let (lhs, rhs, state) = find_block_collision(hash, &self.state);
+        self.pairs.push((lhs, rhs));
+        self.state = state;
+    }
+
+    /// the number of pairs; the multicollision contains `2^len` messages
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// the state after any of the messages
+    pub fn state(&self) -> &Bytes {
+        &self.state
+    }
+
+    /// the message where bit `i` of `index` selects the block of pair `i`
+    pub fn message(&self, index: usize) -> Bytes {
+        self.pairs
+            .iter()
+            .enumerate()
+            .map(|(i, (lhs, rhs))| if index >> i & 1 == 0 { lhs } else { rhs })
+            .fold(Bytes::from(vec![]), |message, block| {
+                message + block.clone()
+            })
+    }
+
+    /// runs all messages through the compression function of `other`,
+    /// starting in its initial state, and returns the resulting states in the
+    /// order of the message indices
+    ///
+    /// The messages share their prefixes, so this needs `2^(n+1)`
+    /// compressions instead of `n * 2^n`.
+    fn states_under(&self, other: &MdHash) -> Vec<Bytes> {
+        let mut states = vec![other.initial_state()];
+        for (lhs, rhs) in &self.pairs {
+            let left: Vec<Bytes> = states.iter().map(|s| other.compress(s, &lhs[..])).collect();
+            let right: Vec<Bytes> = states.iter().map(|s| other.compress(s, &rhs[..])).collect();
+            states = [left, right].concat();
+        }
+        states
+    }
+}
+
+/// finds two different messages which collide under both `cheap` and
+/// `expensive`, and therefore also under the cascade `cheap || expensive`
+///
+/// We generate a multicollision in `cheap` which is large enough to expect a
+/// birthday collision in `expensive`, and extend it by another block until
+/// there actually is one. The messages have the same length, so they also
+/// collide after padding.
+///
+/// ```rust
+/// use cryptopals::{cascade_collision, MdHash};
+/// let (cheap, expensive) = (MdHash::new(16), MdHash::new(24));
+/// let (lhs, rhs) = cascade_collision(&cheap, &expensive);
+/// assert_ne!(lhs, rhs);
+/// assert_eq!(cheap.hash(&lhs), cheap.hash(&rhs));
+/// assert_eq!(expensive.hash(&lhs), expensive.hash(&rhs));
+/// ```
+pub fn cascade_collision(cheap: &MdHash, expensive: &MdHash) -> (Bytes, Bytes) {
+    let mut multicollision = Multicollision::generate(cheap, 4 * expensive.state_size());
+    loop {
+        let mut seen = HashMap::new();
+        for (index, state) in multicollision
+            .states_under(expensive)
+            .into_iter()
+            .enumerate()
+        {
+            if let Some(other) = seen.insert(state, index) {
+                return (multicollision.message(other), multicollision.message(index));
+            }
+        }
+        multicollision.extend(cheap);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{MdHash, Multicollision};
+
+    #[test]
+    fn test_multicollision() {
+        let hash = MdHash::new(16);
+        let multicollision = Multicollision::generate(&hash, 5);
+        assert_eq!(multicollision.len(), 5);
+
+        let messages: HashSet<_> = (0..32).map(|i| multicollision.message(i)).collect();
+        assert_eq!(messages.len(), 32);
+
+        let digests: HashSet<_> = messages.iter().map(|m| hash.hash(m)).collect();
+        assert_eq!(digests.len(), 1);
+    }
+}
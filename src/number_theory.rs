@@ -0,0 +1,298 @@
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+const SMALL_PRIMES: [u32; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+const MILLER_RABIN_ROUNDS: usize = 40;
+
+/// calculates the modular inverse of `a` modulo `m` using the extended
+/// euclidean algorithm, if it exists
+///
+/// ```rust
+/// use cryptopals::invmod;
+/// use num_bigint::BigUint;
+/// assert_eq!(invmod(&BigUint::from(17u32), &BigUint::from(3120u32)), Some(BigUint::from(2753u32)));
+/// assert_eq!(invmod(&BigUint::from(2u32), &BigUint::from(4u32)), None);
+/// ```
+pub fn invmod(a: &BigUint, m: &BigUint) -> Option<BigUint> {
+    // the signs of the bezout coefficients alternate, so we only keep track
+    // of their magnitudes, which is a lot faster than using `BigInt`
+    let (mut old_r, mut r) = (a % m, m.clone());
+    let (mut old_s, mut s) = (BigUint::one(), BigUint::zero());
+    let mut negative = false;
+
+    while !r.is_zero() {
+        let (quotient, remainder) = old_r.div_rem(&r);
+        old_r = std::mem::replace(&mut r, remainder);
+        let next = &old_s + quotient * &s;
+        old_s = std::mem::replace(&mut s, next);
+        negative = !negative;
+    }
+
+    if !old_r.is_one() {
+        None
+    } else if negative {
+        Some((m - old_s) % m)
+    } else {
+        Some(old_s % m)
+    }
+}
+
+/// checks if `n` is prime, using trial division by some small primes and the
+/// Miller-Rabin test
+///
+/// ```rust
+/// use cryptopals::is_probable_prime;
+/// use num_bigint::BigUint;
+/// assert!(is_probable_prime(&BigUint::from(65537u32)));
+/// assert!(!is_probable_prime(&BigUint::from(561u32)));
+/// ```
+pub fn is_probable_prime(n: &BigUint) -> bool {
+    for p in SMALL_PRIMES.iter().map(|p| BigUint::from(*p)) {
+        if *n == p {
+            return true;
+        }
+        if (n % &p).is_zero() {
+            return false;
+        }
+    }
+    if *n < BigUint::from(2u32) {
+        return false;
+    }
+
+    // write n - 1 as 2**r * d
+    let n_minus_one = n - 1u32;
+    let r = n_minus_one.trailing_zeros().unwrap();
+    let d = &n_minus_one >> r;
+
+    let mut rng = rand::thread_rng();
+    'witness: for _ in 0..MILLER_RABIN_ROUNDS {
+        let a = rng.gen_biguint_range(&BigUint::from(2u32), &n_minus_one);
+        let mut x = a.modpow(&d, n);
+        if x.is_one() || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..r {
+            x = x.modpow(&BigUint::from(2u32), n);
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// generates a random prime with exactly `bits` bits, where the two most
+/// significant bits are set (so that the product of two such primes has
+/// exactly `2 * bits` bits)
+pub fn generate_prime(bits: u64) -> BigUint {
+    assert!(bits >= 8);
+    let mut rng = rand::thread_rng();
+    loop {
+        let mut candidate = rng.gen_biguint(bits);
+        candidate.set_bit(bits - 1, true);
+        candidate.set_bit(bits - 2, true);
+        candidate.set_bit(0, true);
+        if is_probable_prime(&candidate) {
+            return candidate;
+        }
+    }
+}
+
+/// solves the system of congruences `x ≡ r_i mod m_i` for pairwise coprime
+/// moduli using the chinese remainder theorem, and returns `x mod (m_1 * ... * m_k)`
+///
+/// ```rust
+/// use cryptopals::crt;
+/// use num_bigint::BigUint;
+/// let residues = [(2u32, 3u32), (3, 5), (2, 7)].map(|(r, m)| (BigUint::from(r), BigUint::from(m)));
+/// assert_eq!(crt(&residues), Some(BigUint::from(23u32)));
+/// ```
+pub fn crt(residues: &[(BigUint, BigUint)]) -> Option<BigUint> {
+    let product: BigUint = residues.iter().map(|(_, m)| m).product();
+    let mut result = BigUint::zero();
+    for (r, m) in residues {
+        let ms = &product / m;
+        result += r * &ms * invmod(&ms, m)?;
+    }
+    Some(result % product)
+}
+
+/// calculates the `n`th root of `x`, if `x` is a perfect `n`th power; the
+/// `0`th root is undefined
+///
+/// ```rust
+/// use cryptopals::exact_root;
+/// use num_bigint::BigUint;
+/// assert_eq!(exact_root(&BigUint::from(1331u32), 3), Some(BigUint::from(11u32)));
+/// assert_eq!(exact_root(&BigUint::from(1332u32), 3), None);
+/// assert_eq!(exact_root(&BigUint::from(1u32), 0), None);
+/// ```
+pub fn exact_root(x: &BigUint, n: u32) -> Option<BigUint> {
+    if n == 0 {
+        return None;
+    }
+
+    let root = x.nth_root(n);
+    if root.pow(n) == *x {
+        Some(root)
+    } else {
+        None
+    }
+}
+
+/// finds the distinct prime factors of `n` below `bound` by trial division
+///
+/// ```rust
+/// use cryptopals::small_prime_factors;
+/// use num_bigint::BigUint;
+/// let n = BigUint::from(2u32 * 2 * 3 * 101 * 65537);
+/// assert_eq!(small_prime_factors(&n, 1000), vec![2, 3, 101]);
+/// ```
+pub fn small_prime_factors(n: &BigUint, bound: u64) -> Vec<u64> {
+    let mut n = n.clone();
+    let mut factors = Vec::new();
+    for candidate in 2..bound {
+        if n.is_one() {
+            break;
+        }
+        // composite candidates never divide, because their prime factors
+        // have already been divided out
+        if (&n % candidate).is_zero() {
+            factors.push(candidate);
+            while (&n % candidate).is_zero() {
+                n /= candidate;
+            }
+        }
+    }
+    factors
+}
+
+/// calculates a square root of `a` modulo the odd prime `p` with the
+/// Tonelli-Shanks algorithm, if `a` is a quadratic residue
+///
+/// ```rust
+/// use cryptopals::sqrt_mod;
+/// use num_bigint::BigUint;
+/// let p = BigUint::from(10009u32);
+/// let root = sqrt_mod(&BigUint::from(1234u32), &p).unwrap();
+/// assert_eq!(&root * &root % &p, BigUint::from(1234u32));
+/// assert_eq!(sqrt_mod(&BigUint::from(7u32), &p), None);
+/// ```
+pub fn sqrt_mod(a: &BigUint, p: &BigUint) -> Option<BigUint> {
+    let a = a % p;
+    if a.is_zero() {
+        return Some(a);
+    }
+    let p_minus_one = p - 1u32;
+    let half = &p_minus_one >> 1;
+    if a.modpow(&half, p) != BigUint::one() {
+        return None;
+    }
+
+    // write p - 1 as 2**s * q
+    let s = p_minus_one.trailing_zeros().unwrap();
+    let q = &p_minus_one >> s;
+
+    // any quadratic non-residue z
+    let mut z = BigUint::from(2u32);
+    while z.modpow(&half, p) != p_minus_one {
+        z += 1u32;
+    }
+
+    let mut m = s;
+    let mut c = z.modpow(&q, p);
+    let mut t = a.modpow(&q, p);
+    let mut root = a.modpow(&((&q + 1u32) >> 1), p);
+    while !t.is_one() {
+        // the least i with t^(2^i) = 1
+        let mut i = 0;
+        let mut t_power = t.clone();
+        while !t_power.is_one() {
+            t_power = &t_power * &t_power % p;
+            i += 1;
+        }
+
+        let b = c.modpow(&(BigUint::one() << (m - i - 1)), p);
+        m = i;
+        c = &b * &b % p;
+        t = t * &c % p;
+        root = root * b % p;
+    }
+    Some(root)
+}
+
+/// computes the discrete logarithm of `h` to the base `g` modulo the prime
+/// `m` with the Pohlig-Hellman algorithm, where `g` is a primitive root and
+/// `m - 1` is the product of the distinct small primes `factors`
+///
+/// For every prime `r`, raising to `order / r` moves `g` and `h` into the
+/// subgroup of order `r`, where we find the logarithm modulo `r` by brute
+/// force. Returns `None` if `h` is not a power of `g`.
+///
+/// ```rust
+/// use cryptopals::pohlig_hellman;
+/// use num_bigint::BigUint;
+/// // 2 * 3 * 5 * 7 + 1 = 211 is prime, and 2 is a primitive root modulo 211
+/// let (g, m) = (BigUint::from(2u32), BigUint::from(211u32));
+/// let h = g.modpow(&BigUint::from(123u32), &m);
+/// assert_eq!(pohlig_hellman(&g, &h, &m, &[2, 3, 5, 7]), Some(BigUint::from(123u32)));
+/// ```
+pub fn pohlig_hellman(g: &BigUint, h: &BigUint, m: &BigUint, factors: &[u64]) -> Option<BigUint> {
+    let order: BigUint = factors.iter().product();
+    let mut residues = Vec::new();
+    for r in factors {
+        let exponent = &order / r;
+        let g_r = g.modpow(&exponent, m);
+        let h_r = h.modpow(&exponent, m);
+
+        let mut power = BigUint::one();
+        let residue = (0..*r).find(|_| {
+            let found = power == h_r;
+            power = &power * &g_r % m;
+            found
+        })?;
+        residues.push((BigUint::from(residue), BigUint::from(*r)));
+    }
+    crt(&residues)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use crate::invmod;
+
+    fn invmod_u32(a: u32, m: u32) -> Option<u32> {
+        invmod(&BigUint::from(a), &BigUint::from(m)).map(|inverse| inverse.try_into().unwrap())
+    }
+
+    #[test]
+    fn test_invmod_signs() {
+        // the bezout coefficient of 17 is negative, and the one of 2753 positive
+        assert_eq!(invmod_u32(17, 3120), Some(2753));
+        assert_eq!(invmod_u32(2753, 3120), Some(17));
+
+        for m in [7, 11, 40, 97] {
+            for a in 0..2 * m {
+                match invmod_u32(a, m) {
+                    Some(inverse) => {
+                        assert!(inverse < m);
+                        assert_eq!(a * inverse % m, 1);
+                    }
+                    None => assert!(a % m == 0 || (1..m).all(|x| a * x % m != 1)),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_invmod_modulus_one() {
+        assert_eq!(invmod_u32(0, 1), Some(0));
+        assert_eq!(invmod_u32(5, 1), Some(0));
+    }
+}
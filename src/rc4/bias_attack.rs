@@ -0,0 +1,135 @@
+use rayon::prelude::*;
+
+use crate::{Bytes, Rc4};
+
+/// an oracle which encrypts `request || cookie` with RC4 under a fresh
+/// random key for every request (challenge 56)
+pub struct Rc4CookieOracle {
+    cookie: Bytes,
+}
+
+impl Rc4CookieOracle {
+    pub fn new(cookie: Bytes) -> Self {
+        Self { cookie }
+    }
+
+    pub fn encrypt(&self, request: &[u8]) -> Bytes {
+        let key: [u8; 16] = rand::random();
+        Rc4::new(&key).apply(&(Bytes::from(request) + self.cookie.clone()))
+    }
+}
+
+/// the keystream positions of the single-byte biases we use, together with
+/// the value each position is biased towards
+///
+/// The 16th byte of the keystream is biased towards 240 and the 32nd byte
+/// towards 224; the bias of the 16th byte is the stronger one.
+const BIASES: [(usize, u8); 2] = [(15, 240), (31, 224)];
+
+/// recovers the cookie of `oracle` from the single-byte biases of the RC4
+/// keystream
+///
+/// For every prefix length `p` from 0 to 15, the oracle encrypts `samples`
+/// requests consisting of `p` filler bytes, so the cookie bytes `15 - p` and
+/// `31 - p` end up at the biased keystream positions. The most frequent
+/// ciphertext byte at a biased position is most likely the cookie byte xored
+/// with the biased value. Only the first 32 bytes of the cookie
+/// can be recovered this way, and prefix lengths which don't move any cookie
+/// byte to a biased position are skipped.
+///
+/// The biases are tiny, so a reliable recovery needs about `2^24` samples
+/// per prefix length. The samples are spread across all CPU cores.
+pub fn recover_rc4_cookie(oracle: &Rc4CookieOracle, samples: usize) -> Bytes {
+    let cookie_length = oracle.encrypt(&[]).len();
+    // shifting the cookie by up to 15 bytes aligns each of its first 32 bytes
+    // with one of the biased positions
+    let prefixes = BIASES[0].0 + 1;
+    let cookie_index = |position: usize, prefix_length| {
+        position
+            .checked_sub(prefix_length)
+            .filter(|index| *index < cookie_length)
+    };
+
+    // the accumulated scores of every candidate for every cookie byte
+    let mut scores = vec![[0u64; 256]; cookie_length];
+    for prefix_length in 0..prefixes {
+        if BIASES
+            .iter()
+            .all(|(position, _)| cookie_index(*position, prefix_length).is_none())
+        {
+            continue;
+        }
+
+        let request = vec![b'A'; prefix_length];
+        let counts = (0..samples)
+            .into_par_iter()
+            .fold(
+                || [[0u64; 256]; BIASES.len()],
+                |mut counts, _| {
+                    let encrypted = oracle.encrypt(&request);
+                    for (counts, (position, _)) in counts.iter_mut().zip(BIASES) {
+                        if position < encrypted.len() {
+                            counts[encrypted[position] as usize] += 1;
+                        }
+                    }
+                    counts
+                },
+            )
+            .reduce(
+                || [[0u64; 256]; BIASES.len()],
+                |mut lhs, rhs| {
+                    for (lhs, rhs) in lhs.iter_mut().zip(rhs) {
+                        for (l, r) in lhs.iter_mut().zip(rhs) {
+                            *l += r;
+                        }
+                    }
+                    lhs
+                },
+            );
+
+        for (counts, (position, value)) in counts.iter().zip(BIASES) {
+            let Some(index) = cookie_index(position, prefix_length) else {
+                continue;
+            };
+            for (candidate, score) in scores[index].iter_mut().enumerate() {
+                *score += counts[candidate ^ value as usize];
+            }
+        }
+    }
+
+    Bytes::from(
+        scores
+            .iter()
+            .map(|scores| (0..=255u8).max_by_key(|c| scores[*c as usize]).unwrap())
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{recover_rc4_cookie, Bytes, Rc4CookieOracle};
+
+    #[test]
+    fn test_oracle() {
+        let oracle = Rc4CookieOracle::new(Bytes::from_ascii("secret"));
+        assert_eq!(oracle.encrypt(b"request").len(), 13);
+        assert_ne!(oracle.encrypt(b"request"), oracle.encrypt(b"request"));
+    }
+
+    #[test]
+    fn test_reduced_attack() {
+        // a single byte is only moved to the 16th position, so this only needs
+        // one prefix length
+        let cookie = Bytes::from_ascii("!");
+        let oracle = Rc4CookieOracle::new(cookie.clone());
+        assert_eq!(recover_rc4_cookie(&oracle, 1 << 24), cookie);
+    }
+
+    #[test]
+    #[ignore = "takes hours; run with --release --ignored"]
+    fn test_attack() {
+        let cookie = Bytes::from_ascii("BE SURE TO DRINK YOUR OVALTINE");
+        let oracle = Rc4CookieOracle::new(cookie.clone());
+        assert_eq!(recover_rc4_cookie(&oracle, 1 << 24), cookie);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::Bytes;
+
+mod bias_attack;
+pub use bias_attack::*;
+
+/// the RC4 stream cipher
+#[derive(Clone)]
+pub struct Rc4 {
+    state: [u8; 256],
+    i: u8,
+    j: u8,
+}
+
+impl Rc4 {
+    /// runs the key schedule for `key`, which must be between 1 and 256
+    /// bytes long
+    pub fn new(key: &[u8]) -> Self {
+        assert!(
+            (1..=256).contains(&key.len()),
+            "RC4 keys must be between 1 and 256 bytes long"
+        );
+
+        let mut state = [0; 256];
+        for (i, s) in state.iter_mut().enumerate() {
+            *s = i as u8;
+        }
+        let mut j = 0u8;
+        for (i, k) in (0..256).zip(key.iter().cycle()) {
+            j = j.wrapping_add(state[i]).wrapping_add(*k);
+            state.swap(i, j as usize);
+        }
+
+        Self { state, i: 0, j: 0 }
+    }
+
+    /// the next byte of the keystream
+    pub fn next_byte(&mut self) -> u8 {
+        self.i = self.i.wrapping_add(1);
+        self.j = self.j.wrapping_add(self.state[self.i as usize]);
+        self.state.swap(self.i as usize, self.j as usize);
+        let index = self.state[self.i as usize].wrapping_add(self.state[self.j as usize]);
+        self.state[index as usize]
+    }
+
+    /// encrypts or decrypts `data` with the next bytes of the keystream
+    ///
+    /// ```rust
+    /// use cryptopals::{Bytes, Rc4};
+    /// let encrypted = Rc4::new(b"Key").apply(&Bytes::from_ascii("Plaintext"));
+    /// assert_eq!(encrypted.to_hex(), "bbf316e8d940af0ad3");
+    /// ```
+    pub fn apply(&mut self, data: &Bytes) -> Bytes {
+        Bytes::from(
+            data[..]
+                .iter()
+                .map(|b| b ^ self.next_byte())
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bytes, Rc4};
+
+    #[test]
+    fn test_rc4() {
+        // the test vectors from Wikipedia
+        let tests = [
+            ("Key", "Plaintext", "bbf316e8d940af0ad3"),
+            ("Wiki", "pedia", "1021bf0420"),
+            ("Secret", "Attack at dawn", "45a01f645fc35b383552544b9bf5"),
+        ];
+        for (key, plaintext, ciphertext) in tests {
+            let encrypted = Rc4::new(key.as_bytes()).apply(&Bytes::from_ascii(plaintext));
+            assert_eq!(encrypted.to_hex(), ciphertext);
+            assert_eq!(
+                Rc4::new(key.as_bytes()).apply(&encrypted),
+                Bytes::from_ascii(plaintext)
+            );
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use thiserror::Error;
+
+use crate::{crt, exact_root, RsaPublicKey};
+
+#[derive(Error, Debug)]
+pub enum BroadcastAttackError {
+    #[error("no ciphertexts were given")]
+    NoCiphertexts,
+
+    #[error("the public exponent must be small enough to be used as integer")]
+    ExponentTooLarge,
+
+    #[error("all public keys must use the same exponent")]
+    DifferentExponents,
+
+    #[error("need {needed} ciphertexts, but only {found} were given")]
+    NotEnoughCiphertexts { needed: usize, found: usize },
+
+    #[error("the moduli are not pairwise coprime")]
+    ModuliNotCoprime,
+
+    #[error("the combined ciphertext is not a perfect power; the plaintexts probably differ")]
+    NoExactRoot,
+}
+
+/// recovers a message which has been encrypted with `e` different public keys
+/// with the same small exponent `e` (challenge 40)
+///
+/// Because `m**e` is smaller than the product of all moduli, combining the
+/// ciphertexts using the CRT yields `m**e` without any modular reduction, so
+/// that `m` can be calculated using an integer root.
+///
+/// ```rust
+/// use cryptopals::{rsa_broadcast_attack, RsaPrivateKey};
+/// use num_bigint::BigUint;
+/// let m = BigUint::from(0xc0ffeeu32);
+/// let ciphertexts: Vec<_> = (0..3)
+///     .map(|_| RsaPrivateKey::generate(256, 3).public_key())
+///     .map(|key| { let c = key.encrypt(&m).unwrap(); (key, c) })
+///     .collect();
+/// assert_eq!(rsa_broadcast_attack(&ciphertexts).unwrap(), m);
+/// ```
+pub fn rsa_broadcast_attack(
+    ciphertexts: &[(RsaPublicKey, BigUint)],
+) -> Result<BigUint, BroadcastAttackError> {
+    let (first_key, _) = ciphertexts
+        .first()
+        .ok_or(BroadcastAttackError::NoCiphertexts)?;
+    if ciphertexts.iter().any(|(key, _)| key.e() != first_key.e()) {
+        return Err(BroadcastAttackError::DifferentExponents);
+    }
+
+    let e = first_key
+        .e()
+        .to_u32()
+        .ok_or(BroadcastAttackError::ExponentTooLarge)?;
+    let needed = usize::try_from(e).unwrap();
+    if ciphertexts.len() < needed {
+        return Err(BroadcastAttackError::NotEnoughCiphertexts {
+            needed,
+            found: ciphertexts.len(),
+        });
+    }
+
+    let residues: Vec<_> = ciphertexts[..needed]
+        .iter()
+        .map(|(key, c)| (c.clone(), key.n().clone()))
+        .collect();
+    let combined = crt(&residues).ok_or(BroadcastAttackError::ModuliNotCoprime)?;
+
+    exact_root(&combined, e).ok_or(BroadcastAttackError::NoExactRoot)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use crate::{rsa_broadcast_attack, BroadcastAttackError, Bytes, RsaPrivateKey, RsaPublicKey};
+
+    fn encrypt_with_new_keys(m: &BigUint, e: u32, count: usize) -> Vec<(RsaPublicKey, BigUint)> {
+        (0..count)
+            .map(|_| RsaPrivateKey::generate(512, e).public_key())
+            .map(|key| {
+                let c = key.encrypt(m).unwrap();
+                (key, c)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_e3() {
+        let m = Bytes::from_ascii("this is a broadcast message").to_biguint();
+        let ciphertexts = encrypt_with_new_keys(&m, 3, 3);
+        assert_eq!(rsa_broadcast_attack(&ciphertexts).unwrap(), m);
+    }
+
+    #[test]
+    fn test_e5() {
+        let m = Bytes::from_ascii("e does not need to be 3").to_biguint();
+        let ciphertexts = encrypt_with_new_keys(&m, 5, 5);
+        assert_eq!(rsa_broadcast_attack(&ciphertexts).unwrap(), m);
+    }
+
+    #[test]
+    fn test_not_enough_ciphertexts() {
+        let m = BigUint::from(1234u32);
+        let ciphertexts = encrypt_with_new_keys(&m, 3, 2);
+        assert!(matches!(
+            rsa_broadcast_attack(&ciphertexts),
+            Err(BroadcastAttackError::NotEnoughCiphertexts {
+                needed: 3,
+                found: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_zero_exponent() {
+        let key = RsaPublicKey::new(BigUint::from(0u32), BigUint::from(3233u32));
+        assert!(matches!(
+            rsa_broadcast_attack(&[(key, BigUint::from(1u32))]),
+            Err(BroadcastAttackError::NoExactRoot)
+        ));
+    }
+}
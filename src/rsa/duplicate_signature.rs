@@ -0,0 +1,162 @@
+use lazy_static::lazy_static;
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::seq::SliceRandom;
+
+use crate::{
+    crt, encode_sha1_signature_block, is_probable_prime, pohlig_hellman, Bytes, RsaPrivateKey,
+    RsaPublicKey,
+};
+
+/// the bound for the prime factors of `p - 1` and `q - 1` of forged keys
+const SMOOTH_FACTOR_BOUND: u64 = 1 << 16;
+
+lazy_static! {
+    /// the odd primes below `SMOOTH_FACTOR_BOUND`, found with the sieve of
+    /// Eratosthenes
+    static ref SMALL_PRIMES: Vec<u64> = {
+        let mut composite = vec![false; SMOOTH_FACTOR_BOUND as usize];
+        for i in 2..composite.len() {
+            if !composite[i] {
+                for multiple in (i * i..composite.len()).step_by(i) {
+                    composite[multiple] = true;
+                }
+            }
+        }
+        (3..SMOOTH_FACTOR_BOUND)
+            .filter(|i| !composite[*i as usize])
+            .collect()
+    };
+}
+
+/// generates a prime `p` with exactly `bits` bits and `p >= lower`, such
+/// that `p - 1` is twice a product of distinct small primes which are not in
+/// `excluded`, and `s` is a primitive root modulo `p`
+///
+/// Returns the prime and the prime factors of `p - 1`.
+fn generate_smooth_prime(
+    lower: &BigUint,
+    bits: u64,
+    excluded: &[u64],
+    s: &BigUint,
+) -> (BigUint, Vec<u64>) {
+    let mut rng = rand::thread_rng();
+    let lower = lower.max(&(BigUint::one() << (bits - 1))) - 1u32;
+    let upper = (BigUint::one() << bits) - 1u32;
+    let usable = |factors: &[u64], r: &u64| !factors.contains(r) && !excluded.contains(r);
+    loop {
+        let mut factors = vec![2];
+        let mut product = BigUint::from(2u32);
+        while product.bits() + 16 < bits {
+            let r = *SMALL_PRIMES.choose(&mut rng).unwrap();
+            if usable(&factors, &r) {
+                factors.push(r);
+                product *= r;
+            }
+        }
+
+        // the last factor brings p into the range
+        let (Ok(low), Ok(high)) = (
+            u64::try_from(lower.div_ceil(&product)),
+            u64::try_from(&upper / &product),
+        ) else {
+            continue;
+        };
+        let candidates: Vec<_> = SMALL_PRIMES
+            .iter()
+            .filter(|r| low <= **r && **r <= high && usable(&factors, r))
+            .collect();
+        let Some(r) = candidates.choose(&mut rng) else {
+            continue;
+        };
+        factors.push(**r);
+        let p = product * **r + 1u32;
+
+        if !is_probable_prime(&p) || (s % &p).is_zero() {
+            continue;
+        }
+        let p_minus_one = &p - 1u32;
+        let primitive = factors
+            .iter()
+            .all(|r| !s.modpow(&(&p_minus_one / r), &p).is_one());
+        if primitive {
+            return (p, factors);
+        }
+    }
+}
+
+/// generates a prime `p` like [`generate_smooth_prime`], for which the
+/// discrete logarithm `e` of `m` to the base `s` is invertible modulo `p - 1`
+///
+/// Returns the prime, the prime factors of `p - 1` and `e`.
+fn generate_prime_with_log(
+    lower: &BigUint,
+    bits: u64,
+    excluded: &[u64],
+    s: &BigUint,
+    m: &BigUint,
+) -> (BigUint, Vec<u64>, BigUint) {
+    loop {
+        let (p, factors) = generate_smooth_prime(lower, bits, excluded, s);
+        let e = pohlig_hellman(s, &(m % &p), &p, &factors).expect("s is a primitive root");
+        if e.gcd(&(&p - 1u32)).is_one() {
+            return (p, factors, e);
+        }
+    }
+}
+
+/// creates a new key pair under which `signature` of `message` verifies as
+/// well, given a valid PKCS#1 v1.5 signature under `public_key` (challenge
+/// 61)
+///
+/// Let `m` be the padded message. We need a modulus `N'` and an exponent
+/// `e'` with `s^e' = m mod N'`. We choose primes `p` and `q` for which `p - 1`
+/// and `q - 1` are smooth, and `s` is a primitive root, so the discrete
+/// logarithms of `m` modulo `p` and `q` are easy to compute with the
+/// Pohlig-Hellman algorithm. We keep the primes for which the logarithm is
+/// invertible, and combine both logarithms with the chinese remainder
+/// theorem; `p - 1` and `q - 1` only share the factor 2, and both logarithms
+/// are odd. Returns `None` if the signature is not valid.
+pub fn forge_rsa_key(
+    public_key: &RsaPublicKey,
+    message: &[u8],
+    signature: &Bytes,
+) -> Option<RsaPrivateKey> {
+    if !public_key.verify_pkcs1_sha1(message, signature) {
+        return None;
+    }
+    let n = public_key.n();
+    let s = signature.to_biguint();
+    let m = BigUint::from_bytes_be(&encode_sha1_signature_block(message, public_key.size())?);
+
+    // the forged modulus must be larger than the original one, so the
+    // signature is still in range, but have the same length
+    let q_bits = n.bits() / 2;
+    let p_bits = n.bits() - q_bits;
+    let (p, p_factors, e_p) = generate_prime_with_log(&((n >> q_bits) + 1u32), p_bits, &[], &s, &m);
+    let (q, _, e_q) = generate_prime_with_log(&(n / &p + 1u32), q_bits, &p_factors, &s, &m);
+
+    let half_q_order = (&q - 1u32) >> 1;
+    let e = crt(&[(e_p, &p - 1u32), (e_q % &half_q_order, half_q_order)])?;
+    RsaPrivateKey::from_primes(&p, &q, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{forge_rsa_key, RsaPrivateKey};
+
+    #[test]
+    fn test_forge_rsa_key() {
+        let key = RsaPrivateKey::generate(512, 3);
+        let message = b"hi mom";
+        let signature = key.sign_pkcs1_sha1(message).unwrap();
+        assert!(forge_rsa_key(&key.public_key(), b"hi dad", &signature).is_none());
+
+        let forged = forge_rsa_key(&key.public_key(), message, &signature).unwrap();
+        assert_ne!(forged.public_key(), key.public_key());
+        assert!(forged.public_key().verify_pkcs1_sha1(message, &signature));
+        assert!(!forged.public_key().verify_pkcs1_sha1(b"hi dad", &signature));
+        assert_eq!(forged.sign_pkcs1_sha1(message), Some(signature));
+    }
+}
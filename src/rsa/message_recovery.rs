@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::One;
+use openssl::sha::sha256;
+use thiserror::Error;
+
+use crate::{invmod, RsaError, RsaPrivateKey, RsaPublicKey};
+
+#[derive(Error, Debug)]
+pub enum DecryptionServerError {
+    #[error("this ciphertext has already been decrypted")]
+    AlreadyDecrypted,
+
+    #[error(transparent)]
+    Rsa(#[from] RsaError),
+}
+
+/// a server which decrypts every ciphertext only once (challenge 41)
+pub struct RsaDecryptionServer {
+    key: RsaPrivateKey,
+    seen_hashes: HashSet<[u8; 32]>,
+    queries: usize,
+}
+
+impl RsaDecryptionServer {
+    pub fn new(key: RsaPrivateKey) -> Self {
+        Self {
+            key,
+            seen_hashes: HashSet::new(),
+            queries: 0,
+        }
+    }
+
+    pub fn public_key(&self) -> RsaPublicKey {
+        self.key.public_key()
+    }
+
+    /// the number of decryption requests this server has received so far
+    pub fn queries(&self) -> usize {
+        self.queries
+    }
+
+    pub fn decrypt(&mut self, c: &BigUint) -> Result<BigUint, DecryptionServerError> {
+        self.queries += 1;
+        if !self.seen_hashes.insert(sha256(&c.to_bytes_be())) {
+            return Err(DecryptionServerError::AlreadyDecrypted);
+        }
+        Ok(self.key.decrypt(c)?)
+    }
+}
+
+/// recovers the plaintext of `c`, even if `server` has already decrypted it,
+/// by blinding the ciphertext with `s**e`
+pub fn recover_unpadded_message(
+    server: &mut RsaDecryptionServer,
+    c: &BigUint,
+) -> Result<BigUint, DecryptionServerError> {
+    let public_key = server.public_key();
+    let n = public_key.n();
+
+    // s must be invertible modulo n, which is practically always the case
+    let s = loop {
+        let s = rand::thread_rng().gen_biguint_range(&BigUint::from(2u32), n);
+        if s.gcd(n).is_one() {
+            break s;
+        }
+    };
+
+    let blinded = public_key.encrypt(&s)? * c % n;
+    let p = server.decrypt(&blinded)?;
+    Ok(p * invmod(&s, n).unwrap() % n)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use crate::{
+        recover_unpadded_message, DecryptionServerError, RsaDecryptionServer, RsaPrivateKey,
+    };
+
+    #[test]
+    fn test_server_rejects_replay() {
+        let mut server = RsaDecryptionServer::new(RsaPrivateKey::generate(512, 65537));
+        let m = BigUint::from(1337u32);
+        let c = server.public_key().encrypt(&m).unwrap();
+
+        assert_eq!(server.decrypt(&c).unwrap(), m);
+        assert!(matches!(
+            server.decrypt(&c),
+            Err(DecryptionServerError::AlreadyDecrypted)
+        ));
+        assert_eq!(server.queries(), 2);
+    }
+
+    #[test]
+    fn test_message_recovery() {
+        let mut server = RsaDecryptionServer::new(RsaPrivateKey::generate(512, 65537));
+        let m = BigUint::from(0xdeadbeefu32);
+        let c = server.public_key().encrypt(&m).unwrap();
+        server.decrypt(&c).unwrap();
+
+        assert_eq!(recover_unpadded_message(&mut server, &c).unwrap(), m);
+        assert_eq!(server.queries(), 2);
+    }
+}
@@ -0,0 +1,277 @@
+use std::{fmt::Display, str::FromStr};
+
+use getset::Getters;
+use num_bigint::BigUint;
+use thiserror::Error;
+
+use crate::{generate_prime, invmod, Bytes};
+
+mod broadcast;
+pub use broadcast::*;
+
+mod message_recovery;
+pub use message_recovery::*;
+
+mod pkcs1;
+pub use pkcs1::*;
+
+mod signature_forgery;
+pub use signature_forgery::*;
+
+mod parity_oracle;
+pub use parity_oracle::*;
+
+mod padding_oracle;
+pub use padding_oracle::*;
+
+mod duplicate_signature;
+pub use duplicate_signature::*;
+
+#[derive(Error, Debug)]
+pub enum RsaError {
+    #[error("the message is too long for the modulus")]
+    MessageTooLong,
+
+    #[error("invalid key format: {0}")]
+    InvalidKeyFormat(String),
+
+    #[error("invalid PKCS#1 v1.5 padding")]
+    InvalidPadding,
+}
+
+const PUBLIC_KEY_LABEL: &str = "CRYPTOPALS RSA PUBLIC KEY";
+const PRIVATE_KEY_LABEL: &str = "CRYPTOPALS RSA PRIVATE KEY";
+
+/// a textbook RSA public key `[e, n]`
+#[derive(Getters, Clone, Debug, Eq, PartialEq)]
+#[getset(get = "pub")]
+pub struct RsaPublicKey {
+    e: BigUint,
+    n: BigUint,
+}
+
+/// a textbook RSA private key `[d, n]`, which also knows its public exponent
+#[derive(Getters, Clone, Debug, Eq, PartialEq)]
+#[getset(get = "pub")]
+pub struct RsaPrivateKey {
+    e: BigUint,
+    d: BigUint,
+    n: BigUint,
+}
+
+impl RsaPublicKey {
+    pub fn new(e: BigUint, n: BigUint) -> Self {
+        Self { e, n }
+    }
+
+    pub fn encrypt(&self, m: &BigUint) -> Result<BigUint, RsaError> {
+        if *m >= self.n {
+            return Err(RsaError::MessageTooLong);
+        }
+        Ok(m.modpow(&self.e, &self.n))
+    }
+
+    pub fn encrypt_bytes(&self, message: &Bytes) -> Result<Bytes, RsaError> {
+        Ok(Bytes::from(self.encrypt(&message.to_biguint())?))
+    }
+
+    /// the size of the modulus in bytes
+    pub fn size(&self) -> usize {
+        usize::try_from(self.n.bits().div_ceil(8)).unwrap()
+    }
+}
+
+impl RsaPrivateKey {
+    /// generates a key with a modulus of `bits` bits and the public exponent `e`
+    ///
+    /// ```rust
+    /// use cryptopals::{Bytes, RsaPrivateKey};
+    /// let key = RsaPrivateKey::generate(256, 3);
+    /// let message = Bytes::from_ascii("attack at dawn");
+    /// let encrypted = key.public_key().encrypt_bytes(&message).unwrap();
+    /// assert_eq!(key.decrypt_bytes(&encrypted).unwrap(), message);
+    /// ```
+    pub fn generate(bits: u64, e: u32) -> Self {
+        let e = BigUint::from(e);
+        loop {
+            let p = generate_prime(bits / 2);
+            let q = generate_prime(bits - bits / 2);
+            if p == q {
+                continue;
+            }
+
+            if let Some(key) = Self::from_primes(&p, &q, e.clone()) {
+                return key;
+            }
+        }
+    }
+
+    /// creates a private key from the primes `p` and `q`, if `e` is invertible
+    /// modulo `(p - 1) * (q - 1)`
+    pub fn from_primes(p: &BigUint, q: &BigUint, e: BigUint) -> Option<Self> {
+        let et = (p - 1u32) * (q - 1u32);
+        let d = invmod(&e, &et)?;
+        Some(Self { e, d, n: p * q })
+    }
+
+    pub fn public_key(&self) -> RsaPublicKey {
+        RsaPublicKey::new(self.e.clone(), self.n.clone())
+    }
+
+    pub fn decrypt(&self, c: &BigUint) -> Result<BigUint, RsaError> {
+        if *c >= self.n {
+            return Err(RsaError::MessageTooLong);
+        }
+        Ok(c.modpow(&self.d, &self.n))
+    }
+
+    pub fn decrypt_bytes(&self, ciphertext: &Bytes) -> Result<Bytes, RsaError> {
+        Ok(Bytes::from(self.decrypt(&ciphertext.to_biguint())?))
+    }
+
+    /// the size of the modulus in bytes
+    pub fn size(&self) -> usize {
+        usize::try_from(self.n.bits().div_ceil(8)).unwrap()
+    }
+}
+
+/// writes the given fields in a PEM-like format, e.g.
+///
+/// ```text
+/// -----BEGIN CRYPTOPALS RSA PUBLIC KEY-----
+/// e: 03
+/// n: c0ffee...
+/// -----END CRYPTOPALS RSA PUBLIC KEY-----
+/// ```
+fn write_pem(
+    f: &mut std::fmt::Formatter<'_>,
+    label: &str,
+    fields: &[(&str, &BigUint)],
+) -> std::fmt::Result {
+    writeln!(f, "-----BEGIN {label}-----")?;
+    for (name, value) in fields {
+        writeln!(f, "{name}: {}", Bytes::from(*value).to_hex())?;
+    }
+    writeln!(f, "-----END {label}-----")
+}
+
+fn read_pem<const N: usize>(
+    s: &str,
+    label: &str,
+    names: [&str; N],
+) -> Result<[BigUint; N], RsaError> {
+    let mut lines = s.trim().lines().map(str::trim);
+    if lines.next() != Some(&format!("-----BEGIN {label}-----")) {
+        return Err(RsaError::InvalidKeyFormat(format!(
+            "expected begin of {label}"
+        )));
+    }
+
+    let mut values = Vec::with_capacity(N);
+    for name in names {
+        let line = lines
+            .next()
+            .ok_or_else(|| RsaError::InvalidKeyFormat(format!("missing field '{name}'")))?;
+        let value = line
+            .strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or_else(|| RsaError::InvalidKeyFormat(format!("expected field '{name}'")))?;
+        let value = Bytes::from_hex(value.trim())
+            .map_err(|why| RsaError::InvalidKeyFormat(why.to_string()))?;
+        values.push(value.to_biguint());
+    }
+
+    if lines.next() != Some(&format!("-----END {label}-----")) || lines.next().is_some() {
+        return Err(RsaError::InvalidKeyFormat(format!("expected end of {label}")));
+    }
+    Ok(values.try_into().unwrap())
+}
+
+impl Display for RsaPublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_pem(f, PUBLIC_KEY_LABEL, &[("e", &self.e), ("n", &self.n)])
+    }
+}
+
+impl FromStr for RsaPublicKey {
+    type Err = RsaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [e, n] = read_pem(s, PUBLIC_KEY_LABEL, ["e", "n"])?;
+        Ok(Self { e, n })
+    }
+}
+
+impl Display for RsaPrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_pem(
+            f,
+            PRIVATE_KEY_LABEL,
+            &[("e", &self.e), ("d", &self.d), ("n", &self.n)],
+        )
+    }
+}
+
+impl FromStr for RsaPrivateKey {
+    type Err = RsaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [e, d, n] = read_pem(s, PRIVATE_KEY_LABEL, ["e", "d", "n"])?;
+        Ok(Self { e, d, n })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use num_bigint::BigUint;
+
+    use crate::{Bytes, RsaError, RsaPrivateKey, RsaPublicKey};
+
+    #[test]
+    fn test_roundtrip() {
+        for e in [3, 65537] {
+            let key = RsaPrivateKey::generate(512, e);
+            assert_eq!(key.n().bits(), 512);
+
+            let m = BigUint::from(42u32);
+            let c = key.public_key().encrypt(&m).unwrap();
+            assert_eq!(key.decrypt(&c).unwrap(), m);
+        }
+    }
+
+    #[test]
+    fn test_message_too_long() {
+        let key = RsaPrivateKey::generate(128, 3);
+        let message = Bytes::from_ascii("this message is way too long");
+        assert!(matches!(
+            key.public_key().encrypt_bytes(&message),
+            Err(RsaError::MessageTooLong)
+        ));
+    }
+
+    #[test]
+    fn test_serialization() {
+        let key = RsaPrivateKey::generate(256, 65537);
+        assert_eq!(key.to_string().parse::<RsaPrivateKey>().unwrap(), key);
+        assert_eq!(
+            key.public_key().to_string().parse::<RsaPublicKey>().unwrap(),
+            key.public_key()
+        );
+        assert!(key.public_key().to_string().parse::<RsaPrivateKey>().is_err());
+    }
+
+    #[test]
+    fn test_fixture() {
+        let key: RsaPrivateKey = fs::read_to_string("data/rsa_512_e3.key")
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(key.n().bits(), 512);
+
+        let message = Bytes::from_ascii("YELLOW SUBMARINE");
+        let encrypted = key.public_key().encrypt_bytes(&message).unwrap();
+        assert_eq!(key.decrypt_bytes(&encrypted).unwrap(), message);
+    }
+}
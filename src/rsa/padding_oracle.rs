@@ -0,0 +1,175 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::One;
+
+use crate::{RsaError, RsaPrivateKey, RsaPublicKey};
+
+/// an oracle which tells whether the plaintext of a ciphertext starts with
+/// `00 02`, i.e. if it is PKCS#1 v1.5 conforming (challenges 47 and 48)
+pub struct Pkcs1PaddingOracle {
+    key: RsaPrivateKey,
+    queries: usize,
+}
+
+impl Pkcs1PaddingOracle {
+    pub fn new(key: RsaPrivateKey) -> Self {
+        Self { key, queries: 0 }
+    }
+
+    pub fn public_key(&self) -> RsaPublicKey {
+        self.key.public_key()
+    }
+
+    /// the number of queries this oracle has answered so far
+    pub fn queries(&self) -> usize {
+        self.queries
+    }
+
+    pub fn is_conforming(&mut self, c: &BigUint) -> Result<bool, RsaError> {
+        self.queries += 1;
+        let m = self.key.decrypt(c)?;
+
+        // the plaintext starts with 00 02 if 2B <= m < 3B
+        let b = BigUint::one() << (8 * (self.key.size() - 2));
+        Ok(&b * 2u32 <= m && m < &b * 3u32)
+    }
+}
+
+/// a set of disjoint closed intervals, which are kept sorted
+struct IntervalSet(Vec<(BigUint, BigUint)>);
+
+impl IntervalSet {
+    fn insert(&mut self, lower: BigUint, upper: BigUint) {
+        self.0.push((lower, upper));
+        self.0.sort();
+
+        let mut merged: Vec<(BigUint, BigUint)> = Vec::with_capacity(self.0.len());
+        for (lower, upper) in self.0.drain(..) {
+            match merged.last_mut() {
+                Some((_, last_upper)) if lower <= *last_upper => {
+                    if upper > *last_upper {
+                        *last_upper = upper;
+                    }
+                }
+                _ => merged.push((lower, upper)),
+            }
+        }
+        self.0 = merged;
+    }
+}
+
+/// Bleichenbacher's attack from "Chosen Ciphertext Attacks Against Protocols
+/// Based on the RSA Encryption Standard PKCS #1" (1998), which recovers the
+/// padded plaintext of the PKCS#1 conforming ciphertext `c`
+pub fn bleichenbacher_attack(
+    oracle: &mut Pkcs1PaddingOracle,
+    c: &BigUint,
+) -> Result<BigUint, RsaError> {
+    let public_key = oracle.public_key();
+    let n = public_key.n();
+    let b = BigUint::one() << (8 * (public_key.size() - 2));
+    let b2 = &b * 2u32;
+    let b3 = &b * 3u32;
+
+    let mut is_conforming_with = |s: &BigUint| -> Result<bool, RsaError> {
+        let c_prime = public_key.encrypt(s)? * c % n;
+        oracle.is_conforming(&c_prime)
+    };
+
+    // step 1 is not necessary, because c is already PKCS#1 conforming
+    let mut intervals = IntervalSet(vec![(b2.clone(), &b3 - 1u32)]);
+
+    // step 2a: search for the smallest s >= n / 3B which yields a conforming plaintext
+    let mut s = n.div_ceil(&b3);
+    while !is_conforming_with(&s)? {
+        s += 1u32;
+    }
+
+    loop {
+        // step 3: narrow the set of solutions
+        let mut narrowed = IntervalSet(Vec::new());
+        for (a, upper) in &intervals.0 {
+            let r_min = (a * &s + 1u32 - &b3).div_ceil(n);
+            let r_max = (upper * &s - &b2) / n;
+
+            let mut r = r_min;
+            while r <= r_max {
+                let rn = &r * n;
+                let new_lower = (&b2 + &rn).div_ceil(&s).max(a.clone());
+                let new_upper = ((&b3 - 1u32 + &rn) / &s).min(upper.clone());
+                if new_lower <= new_upper {
+                    narrowed.insert(new_lower, new_upper);
+                }
+                r += 1u32;
+            }
+        }
+        intervals = narrowed;
+
+        // step 4: compute the solution
+        if let [(a, upper)] = &intervals.0[..] {
+            if a == upper {
+                return Ok(a.clone());
+            }
+        }
+
+        if intervals.0.len() > 1 {
+            // step 2b: searching with more than one interval left
+            s += 1u32;
+            while !is_conforming_with(&s)? {
+                s += 1u32;
+            }
+        } else {
+            // step 2c: searching with one interval left
+            let (a, upper) = &intervals.0[0];
+            let mut r = ((upper * &s - &b2) * 2u32).div_ceil(n);
+            'search: loop {
+                let rn = &r * n;
+                let s_min = (&b2 + &rn).div_ceil(upper);
+                let s_max = (&b3 + &rn).div_ceil(a);
+
+                let mut candidate = s_min;
+                while candidate < s_max {
+                    if is_conforming_with(&candidate)? {
+                        s = candidate;
+                        break 'search;
+                    }
+                    candidate += 1u32;
+                }
+                r += 1u32;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bleichenbacher_attack, Bytes, Pkcs1PaddingOracle, RsaPrivateKey};
+
+    fn run_attack(bits: u64, message: &str) {
+        let mut oracle = Pkcs1PaddingOracle::new(RsaPrivateKey::generate(bits, 3));
+        let public_key = oracle.public_key();
+        let message = Bytes::from_ascii(message);
+        let c = public_key.encrypt_pkcs1(&message).unwrap();
+        assert!(oracle.is_conforming(&c.to_biguint()).unwrap());
+
+        let padded = bleichenbacher_attack(&mut oracle, &c.to_biguint()).unwrap();
+        assert_eq!(
+            public_key.encrypt(&padded).unwrap(),
+            c.to_biguint(),
+            "the recovered plaintext does not encrypt to the ciphertext"
+        );
+        assert!(Bytes::from(padded)[..].ends_with(&message[..]));
+        assert!(oracle.queries() > 1);
+    }
+
+    #[test]
+    fn test_256_bit_modulus() {
+        run_attack(256, "kick it, CC");
+    }
+
+    #[test]
+    #[ignore = "takes a while; run with --ignored"]
+    fn test_768_bit_modulus() {
+        run_attack(768, "kick it, CC");
+    }
+}
@@ -0,0 +1,106 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+use crate::{RsaError, RsaPrivateKey, RsaPublicKey};
+
+/// an oracle which tells whether the plaintext of a ciphertext is even
+/// (challenge 46)
+pub struct RsaParityOracle {
+    key: RsaPrivateKey,
+    queries: usize,
+}
+
+impl RsaParityOracle {
+    pub fn new(key: RsaPrivateKey) -> Self {
+        Self { key, queries: 0 }
+    }
+
+    pub fn public_key(&self) -> RsaPublicKey {
+        self.key.public_key()
+    }
+
+    /// the number of queries this oracle has answered so far
+    pub fn queries(&self) -> usize {
+        self.queries
+    }
+
+    pub fn is_even(&mut self, c: &BigUint) -> Result<bool, RsaError> {
+        self.queries += 1;
+        Ok(self.key.decrypt(c)?.is_even())
+    }
+}
+
+/// recovers the plaintext of `c` using a parity oracle
+///
+/// Multiplying the ciphertext by `2**e` doubles the plaintext. Because `n` is
+/// odd, the doubled plaintext is even if it did not wrap around the modulus,
+/// which means that the plaintext is in the lower half of the current
+/// interval. After `log2(n)` steps the interval contains a single number.
+///
+/// The interval is kept as `[k * n / 2**i, (k + 1) * n / 2**i)`, so that no
+/// precision is lost. After every step, `progress` receives the current upper
+/// bound of the plaintext.
+pub fn rsa_parity_attack(
+    oracle: &mut RsaParityOracle,
+    c: &BigUint,
+    mut progress: impl FnMut(&BigUint),
+) -> Result<BigUint, RsaError> {
+    let public_key = oracle.public_key();
+    let n = public_key.n();
+    let double = public_key.encrypt(&BigUint::from(2u32))?;
+
+    let mut k = BigUint::zero();
+    let mut denominator = BigUint::one();
+    let mut c = c.clone();
+
+    for _ in 0..n.bits() {
+        c = c * &double % n;
+        k <<= 1;
+        denominator <<= 1;
+        if !oracle.is_even(&c)? {
+            k += 1u32;
+        }
+        progress(&((&k + 1u32) * n / &denominator));
+    }
+
+    Ok((k + 1u32) * n / denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use crate::{rsa_parity_attack, Bytes, RsaParityOracle, RsaPrivateKey};
+
+    #[test]
+    fn test_parity_attack() {
+        let mut oracle = RsaParityOracle::new(RsaPrivateKey::generate(384, 65537));
+        let public_key = oracle.public_key();
+
+        for message in [
+            Bytes::from_ascii("That's why I found you don't play around"),
+            Bytes::from(vec![0x01]),
+            Bytes::from(public_key.n() - 1u32),
+        ] {
+            let c = public_key.encrypt(&message.to_biguint()).unwrap();
+            let mut steps = 0;
+            let recovered = rsa_parity_attack(&mut oracle, &c, |_| steps += 1).unwrap();
+            assert_eq!(Bytes::from(recovered), message);
+            assert_eq!(steps, 384);
+        }
+        assert_eq!(oracle.queries(), 3 * 384);
+    }
+
+    #[test]
+    fn test_upper_bound_decreases() {
+        let mut oracle = RsaParityOracle::new(RsaPrivateKey::generate(256, 3));
+        let m = BigUint::from(4711u32);
+        let c = oracle.public_key().encrypt(&m).unwrap();
+
+        let mut bounds = Vec::new();
+        rsa_parity_attack(&mut oracle, &c, |bound| bounds.push(bound.clone())).unwrap();
+        assert!(bounds.windows(2).all(|w| w[0] >= w[1]));
+        assert!(bounds.iter().all(|bound| *bound >= m));
+    }
+}
@@ -0,0 +1,160 @@
+use num_bigint::BigUint;
+use openssl::sha::sha1;
+use rand::Rng;
+
+use crate::{Bytes, RsaError, RsaPrivateKey, RsaPublicKey};
+
+/// the DER encoding of the `DigestInfo` prefix for SHA-1, see
+/// <https://datatracker.ietf.org/doc/html/rfc8017#section-9.2>
+pub const SHA1_DIGEST_INFO: [u8; 15] = [
+    0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14,
+];
+
+const SHA1_LENGTH: usize = 20;
+
+/// converts `value` into a big endian byte string of exactly `length` bytes
+pub(crate) fn to_fixed_length(value: &BigUint, length: usize) -> Option<Vec<u8>> {
+    let bytes = value.to_bytes_be();
+    if bytes.len() > length {
+        return None;
+    }
+    let mut result = vec![0; length - bytes.len()];
+    result.extend(bytes);
+    Some(result)
+}
+
+/// creates the encoded message `00 01 FF ... FF 00 DigestInfo HASH` with a
+/// length of `length` bytes
+pub(crate) fn encode_sha1_signature_block(message: &[u8], length: usize) -> Option<Vec<u8>> {
+    let digest_length = SHA1_DIGEST_INFO.len() + SHA1_LENGTH;
+
+    // we need at least 8 bytes of padding
+    if length < digest_length + 11 {
+        return None;
+    }
+
+    let mut block = vec![0x00, 0x01];
+    block.resize(length - digest_length - 1, 0xff);
+    block.push(0x00);
+    block.extend(SHA1_DIGEST_INFO);
+    block.extend(sha1(message));
+    Some(block)
+}
+
+impl RsaPrivateKey {
+    /// creates a PKCS#1 v1.5 signature of the SHA-1 hash of `message`
+    ///
+    /// ```rust
+    /// use cryptopals::RsaPrivateKey;
+    /// let key = RsaPrivateKey::generate(512, 3);
+    /// let signature = key.sign_pkcs1_sha1(b"hi mom").unwrap();
+    /// assert!(key.public_key().verify_pkcs1_sha1(b"hi mom", &signature));
+    /// assert!(!key.public_key().verify_pkcs1_sha1(b"hi dad", &signature));
+    /// ```
+    pub fn sign_pkcs1_sha1(&self, message: &[u8]) -> Option<Bytes> {
+        let block = encode_sha1_signature_block(message, self.size())?;
+        let s = self
+            .decrypt(&BigUint::from_bytes_be(&block))
+            .expect("the signature block is smaller than the modulus");
+        to_fixed_length(&s, self.size()).map(Bytes::from)
+    }
+
+    /// decrypts `ciphertext` and removes the PKCS#1 v1.5 encryption padding
+    pub fn decrypt_pkcs1(&self, ciphertext: &Bytes) -> Result<Bytes, RsaError> {
+        let block = self.decrypt(&ciphertext.to_biguint())?;
+        let block = to_fixed_length(&block, self.size()).ok_or(RsaError::InvalidPadding)?;
+
+        let rest = block
+            .strip_prefix(&[0x00, 0x02])
+            .ok_or(RsaError::InvalidPadding)?;
+        let separator = rest
+            .iter()
+            .position(|b| *b == 0x00)
+            .ok_or(RsaError::InvalidPadding)?;
+        if separator < 8 {
+            return Err(RsaError::InvalidPadding);
+        }
+        Ok(Bytes::from(&rest[separator + 1..]))
+    }
+}
+
+impl RsaPublicKey {
+    /// pads `message` as `00 02 PS 00 M`, where `PS` consists of at least 8
+    /// random nonzero bytes, and encrypts it
+    ///
+    /// ```rust
+    /// use cryptopals::{Bytes, RsaPrivateKey};
+    /// let key = RsaPrivateKey::generate(256, 3);
+    /// let message = Bytes::from_ascii("kick it, CC");
+    /// let encrypted = key.public_key().encrypt_pkcs1(&message).unwrap();
+    /// assert_eq!(key.decrypt_pkcs1(&encrypted).unwrap(), message);
+    /// ```
+    pub fn encrypt_pkcs1(&self, message: &Bytes) -> Result<Bytes, RsaError> {
+        if message.len() + 11 > self.size() {
+            return Err(RsaError::MessageTooLong);
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut block = vec![0x00, 0x02];
+        block.extend((0..self.size() - message.len() - 3).map(|_| rng.gen_range(1..=0xff)));
+        block.push(0x00);
+        block.extend(&message[..]);
+
+        let c = self.encrypt(&BigUint::from_bytes_be(&block))?;
+        Ok(Bytes::from(to_fixed_length(&c, self.size()).unwrap()))
+    }
+
+    fn signature_block(&self, signature: &Bytes) -> Option<Vec<u8>> {
+        if signature.len() != self.size() {
+            return None;
+        }
+        let block = self.encrypt(&signature.to_biguint()).ok()?;
+        to_fixed_length(&block, self.size())
+    }
+
+    /// verifies a PKCS#1 v1.5 signature of the SHA-1 hash of `message` by
+    /// re-creating the expected signature block and comparing it as a whole
+    pub fn verify_pkcs1_sha1(&self, message: &[u8], signature: &Bytes) -> bool {
+        match (
+            self.signature_block(signature),
+            encode_sha1_signature_block(message, self.size()),
+        ) {
+            (Some(block), Some(expected)) => block == expected,
+            _ => false,
+        }
+    }
+
+    /// verifies a PKCS#1 v1.5 signature of the SHA-1 hash of `message`, but
+    /// only parses the signature block from the left, without checking if the
+    /// hash is right-justified (as many implementations did)
+    ///
+    /// This verifier is vulnerable to Bleichenbacher's e=3 signature forgery,
+    /// see [`forge_pkcs1_sha1_signature`](crate::forge_pkcs1_sha1_signature)
+    pub fn verify_pkcs1_sha1_sloppy(&self, message: &[u8], signature: &Bytes) -> bool {
+        let block = match self.signature_block(signature) {
+            Some(block) => block,
+            None => return false,
+        };
+
+        let rest = match block.strip_prefix(&[0x00, 0x01]) {
+            Some(rest) => rest,
+            None => return false,
+        };
+
+        let padding_length = rest.iter().take_while(|b| **b == 0xff).count();
+        if padding_length == 0 {
+            return false;
+        }
+
+        let rest = match rest[padding_length..]
+            .strip_prefix(&[0x00])
+            .and_then(|rest| rest.strip_prefix(&SHA1_DIGEST_INFO))
+        {
+            Some(rest) => rest,
+            None => return false,
+        };
+
+        // here we should check that there are no more bytes after the hash
+        rest.len() >= SHA1_LENGTH && rest[..SHA1_LENGTH] == sha1(message)
+    }
+}
@@ -0,0 +1,77 @@
+use openssl::sha::sha1;
+
+use crate::{Bytes, RsaPublicKey, SHA1_DIGEST_INFO};
+
+use super::pkcs1::to_fixed_length;
+
+/// forges a PKCS#1 v1.5 signature for `message`, which is accepted by
+/// [`RsaPublicKey::verify_pkcs1_sha1_sloppy`] if the public key uses `e = 3`
+/// (challenge 42)
+///
+/// We create a block `00 01 FF 00 DigestInfo HASH GARBAGE`, where the garbage
+/// is chosen so that the block is a perfect cube. Rounding the cube root of
+/// the block with garbage set to zero up does the trick, as long as the
+/// garbage is long enough to absorb the error.
+///
+/// Returns `None` if the public exponent is not 3, or if the modulus is too
+/// small to hold enough garbage.
+pub fn forge_pkcs1_sha1_signature(public_key: &RsaPublicKey, message: &[u8]) -> Option<Bytes> {
+    if *public_key.e() != 3u32.into() {
+        return None;
+    }
+
+    let mut prefix = vec![0x00, 0x01, 0xff, 0x00];
+    prefix.extend(SHA1_DIGEST_INFO);
+    prefix.extend(sha1(message));
+
+    let mut block = prefix.clone();
+    block.resize(public_key.size(), 0x00);
+
+    let block = Bytes::from(block).to_biguint();
+    let mut root = block.cbrt();
+    if root.pow(3) < block {
+        root += 1u32;
+    }
+
+    // make sure that rounding up did not change the prefix
+    let forged_block = to_fixed_length(&root.pow(3), public_key.size())?;
+    if !forged_block.starts_with(&prefix) {
+        return None;
+    }
+
+    to_fixed_length(&root, public_key.size()).map(Bytes::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{forge_pkcs1_sha1_signature, RsaPrivateKey};
+
+    #[test]
+    fn test_sloppy_verifier_accepts_valid_signature() {
+        let key = RsaPrivateKey::generate(1024, 3);
+        let signature = key.sign_pkcs1_sha1(b"hi mom").unwrap();
+        assert!(key
+            .public_key()
+            .verify_pkcs1_sha1_sloppy(b"hi mom", &signature));
+        assert!(!key
+            .public_key()
+            .verify_pkcs1_sha1_sloppy(b"hi dad", &signature));
+    }
+
+    #[test]
+    fn test_forgery() {
+        let key = RsaPrivateKey::generate(1024, 3).public_key();
+
+        for message in [&b"hi mom"[..], b"transfer $1000000 to eve", b""] {
+            let forged = forge_pkcs1_sha1_signature(&key, message).unwrap();
+            assert!(key.verify_pkcs1_sha1_sloppy(message, &forged));
+            assert!(!key.verify_pkcs1_sha1(message, &forged));
+        }
+    }
+
+    #[test]
+    fn test_forgery_requires_e3() {
+        let key = RsaPrivateKey::generate(1024, 65537).public_key();
+        assert!(forge_pkcs1_sha1_signature(&key, b"hi mom").is_none());
+    }
+}
@@ -0,0 +1,44 @@
+use num_bigint::RandBigInt;
+
+use crate::{hmac_sha256, SrpError, SrpParameters, SrpServer};
+
+use super::{hash_to_int, session_key};
+
+/// an honest SRP client, which knows the password of the user
+pub struct SrpClient {
+    parameters: SrpParameters,
+    email: String,
+    password: String,
+}
+
+impl SrpClient {
+    pub fn new(parameters: SrpParameters, email: &str, password: &str) -> Self {
+        Self {
+            parameters,
+            email: email.to_owned(),
+            password: password.to_owned(),
+        }
+    }
+
+    /// runs the SRP protocol against `server` and returns whether the server
+    /// accepted the login
+    pub fn login(&self, server: &mut SrpServer) -> Result<bool, SrpError> {
+        let n = self.parameters.n();
+        let g = self.parameters.g();
+        let a = rand::thread_rng().gen_biguint_below(n);
+        let a_pub = g.modpow(&a, n);
+
+        let (salt, b_pub) = server.start_handshake(&self.email, &a_pub)?;
+
+        let u = hash_to_int(&a_pub.to_bytes_be(), &b_pub.to_bytes_be());
+        let x = hash_to_int(&salt[..], self.password.as_bytes());
+
+        // S = (B - k * g**x)**(a + u * x) % N
+        let kgx = self.parameters.k() * g.modpow(&x, n) % n;
+        let base = (b_pub + n - kgx) % n;
+        let s = base.modpow(&(a + u * x), n);
+        let k = session_key(&s);
+
+        server.verify(&self.email, &hmac_sha256(&k[..], &salt[..]))
+    }
+}
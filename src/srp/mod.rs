@@ -0,0 +1,59 @@
+use getset::Getters;
+use num_bigint::BigUint;
+use openssl::sha::sha256;
+use thiserror::Error;
+
+use crate::{Bytes, NIST_GENERATOR, NIST_PRIME};
+
+mod server;
+pub use server::*;
+
+mod client;
+pub use client::*;
+
+mod zero_key_client;
+pub use zero_key_client::*;
+
+mod simplified;
+pub use simplified::*;
+
+#[derive(Error, Debug)]
+pub enum SrpError {
+    #[error("unknown user: {0}")]
+    UnknownUser(String),
+
+    #[error("there is no pending handshake for {0}")]
+    NoPendingHandshake(String),
+
+    #[error("the public key sent by the client is not acceptable")]
+    IllegalPublicKey,
+}
+
+/// the parameters which client and server agree on in advance
+#[derive(Getters, Clone)]
+#[getset(get = "pub")]
+pub struct SrpParameters {
+    n: BigUint,
+    g: BigUint,
+    k: BigUint,
+}
+
+impl Default for SrpParameters {
+    fn default() -> Self {
+        Self {
+            n: NIST_PRIME.clone(),
+            g: NIST_GENERATOR.clone(),
+            k: BigUint::from(3u32),
+        }
+    }
+}
+
+/// calculates `SHA256(lhs || rhs)` and interprets the result as integer
+pub(crate) fn hash_to_int(lhs: &[u8], rhs: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(&sha256(&[lhs, rhs].concat()))
+}
+
+/// derives the session key `K = SHA256(S)`
+pub(crate) fn session_key(s: &BigUint) -> Bytes {
+    Bytes::from(&sha256(&s.to_bytes_be())[..])
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::Zero;
+use rand::random;
+
+use crate::{hmac_sha256, Bytes, SrpError, SrpParameters};
+
+use super::{hash_to_int, session_key};
+
+struct UserRecord {
+    salt: Bytes,
+    v: BigUint,
+}
+
+struct Handshake {
+    a_pub: BigUint,
+    b: BigUint,
+    b_pub: BigUint,
+}
+
+/// an SRP server which keeps the password verifiers of its users
+pub struct SrpServer {
+    parameters: SrpParameters,
+    users: HashMap<String, UserRecord>,
+    handshakes: HashMap<String, Handshake>,
+    validate_public_keys: bool,
+}
+
+impl SrpServer {
+    pub fn new(parameters: SrpParameters) -> Self {
+        Self {
+            parameters,
+            users: HashMap::new(),
+            handshakes: HashMap::new(),
+            validate_public_keys: false,
+        }
+    }
+
+    /// creates a server which rejects client public keys `A` with `A ≡ 0 mod N`
+    pub fn hardened(parameters: SrpParameters) -> Self {
+        Self {
+            validate_public_keys: true,
+            ..Self::new(parameters)
+        }
+    }
+
+    pub fn parameters(&self) -> &SrpParameters {
+        &self.parameters
+    }
+
+    /// stores the salt and the password verifier `v = g**x % N` for `email`
+    pub fn register(&mut self, email: &str, password: &str) {
+        let salt = Bytes::from(Vec::from(random::<[u8; 16]>()));
+        let x = hash_to_int(&salt[..], password.as_bytes());
+        let v = self.parameters.g().modpow(&x, self.parameters.n());
+        self.users.insert(email.to_owned(), UserRecord { salt, v });
+    }
+
+    /// receives `email` and `A` from the client, and returns `salt` and `B`
+    pub fn start_handshake(
+        &mut self,
+        email: &str,
+        a_pub: &BigUint,
+    ) -> Result<(Bytes, BigUint), SrpError> {
+        let n = self.parameters.n();
+        if self.validate_public_keys && (a_pub % n).is_zero() {
+            return Err(SrpError::IllegalPublicKey);
+        }
+
+        let user = self
+            .users
+            .get(email)
+            .ok_or_else(|| SrpError::UnknownUser(email.to_owned()))?;
+
+        let b = rand::thread_rng().gen_biguint_below(n);
+        let b_pub = (self.parameters.k() * &user.v + self.parameters.g().modpow(&b, n)) % n;
+
+        let salt = user.salt.clone();
+        self.handshakes.insert(
+            email.to_owned(),
+            Handshake {
+                a_pub: a_pub.clone(),
+                b,
+                b_pub: b_pub.clone(),
+            },
+        );
+        Ok((salt, b_pub))
+    }
+
+    /// checks if the HMAC sent by the client matches `HMAC-SHA256(K, salt)`
+    pub fn verify(&mut self, email: &str, mac: &Bytes) -> Result<bool, SrpError> {
+        let handshake = self
+            .handshakes
+            .remove(email)
+            .ok_or_else(|| SrpError::NoPendingHandshake(email.to_owned()))?;
+        let user = self
+            .users
+            .get(email)
+            .ok_or_else(|| SrpError::UnknownUser(email.to_owned()))?;
+        let n = self.parameters.n();
+
+        let u = hash_to_int(
+            &handshake.a_pub.to_bytes_be(),
+            &handshake.b_pub.to_bytes_be(),
+        );
+
+        // S = (A * v**u) ** b % N
+        let s = (&handshake.a_pub * user.v.modpow(&u, n)).modpow(&handshake.b, n);
+        let k = session_key(&s);
+
+        Ok(hmac_sha256(&k[..], &user.salt[..]) == *mac)
+    }
+}
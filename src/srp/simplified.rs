@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::One;
+use rand::random;
+use rayon::prelude::*;
+
+use crate::{hmac_sha256, Bytes, SrpError, SrpParameters};
+
+use super::{hash_to_int, session_key};
+
+/// the server side of the simplified SRP protocol (challenge 38)
+///
+/// In contrast to the regular SRP, `B` does not depend on the password
+/// verifier, and `u` is a random number chosen by the server.
+pub trait SimplifiedSrpServer {
+    /// receives `email` and `A` from the client, and returns `salt`, `B` and `u`
+    fn start_handshake(
+        &mut self,
+        email: &str,
+        a_pub: &BigUint,
+    ) -> Result<(Bytes, BigUint, BigUint), SrpError>;
+
+    /// checks if the HMAC sent by the client matches `HMAC-SHA256(K, salt)`
+    fn verify(&mut self, email: &str, mac: &Bytes) -> Result<bool, SrpError>;
+}
+
+struct UserRecord {
+    salt: Bytes,
+    v: BigUint,
+}
+
+struct Handshake {
+    a_pub: BigUint,
+    b: BigUint,
+    u: BigUint,
+}
+
+/// a server which implements the simplified SRP protocol correctly
+pub struct HonestSimplifiedServer {
+    parameters: SrpParameters,
+    users: HashMap<String, UserRecord>,
+    handshakes: HashMap<String, Handshake>,
+}
+
+impl HonestSimplifiedServer {
+    pub fn new(parameters: SrpParameters) -> Self {
+        Self {
+            parameters,
+            users: HashMap::new(),
+            handshakes: HashMap::new(),
+        }
+    }
+
+    /// stores the salt and the password verifier `v = g**x % N` for `email`
+    pub fn register(&mut self, email: &str, password: &str) {
+        let salt = Bytes::from(Vec::from(random::<[u8; 16]>()));
+        let x = hash_to_int(&salt[..], password.as_bytes());
+        let v = self.parameters.g().modpow(&x, self.parameters.n());
+        self.users.insert(email.to_owned(), UserRecord { salt, v });
+    }
+}
+
+impl SimplifiedSrpServer for HonestSimplifiedServer {
+    fn start_handshake(
+        &mut self,
+        email: &str,
+        a_pub: &BigUint,
+    ) -> Result<(Bytes, BigUint, BigUint), SrpError> {
+        let user = self
+            .users
+            .get(email)
+            .ok_or_else(|| SrpError::UnknownUser(email.to_owned()))?;
+        let n = self.parameters.n();
+
+        let b = rand::thread_rng().gen_biguint_below(n);
+        let b_pub = self.parameters.g().modpow(&b, n);
+        let u = rand::thread_rng().gen_biguint(128);
+
+        let salt = user.salt.clone();
+        self.handshakes.insert(
+            email.to_owned(),
+            Handshake {
+                a_pub: a_pub.clone(),
+                b,
+                u: u.clone(),
+            },
+        );
+        Ok((salt, b_pub, u))
+    }
+
+    fn verify(&mut self, email: &str, mac: &Bytes) -> Result<bool, SrpError> {
+        let handshake = self
+            .handshakes
+            .remove(email)
+            .ok_or_else(|| SrpError::NoPendingHandshake(email.to_owned()))?;
+        let user = self
+            .users
+            .get(email)
+            .ok_or_else(|| SrpError::UnknownUser(email.to_owned()))?;
+        let n = self.parameters.n();
+
+        // S = (A * v ** u)**b % n
+        let s = (&handshake.a_pub * user.v.modpow(&handshake.u, n)).modpow(&handshake.b, n);
+        let k = session_key(&s);
+
+        Ok(hmac_sha256(&k[..], &user.salt[..]) == *mac)
+    }
+}
+
+struct CapturedLogin {
+    a_pub: BigUint,
+    mac: Option<Bytes>,
+}
+
+/// a malicious server which poses as [`HonestSimplifiedServer`] and captures
+/// the HMAC sent by the client, so that the password can be cracked offline
+///
+/// The server chooses `b = 1`, `u = 1` and an empty salt. The client then
+/// calculates `S = B**(a + u * x) = A * g**x % N`, so that every password
+/// candidate can be checked with a single modular exponentiation.
+pub struct MitmSimplifiedServer {
+    parameters: SrpParameters,
+    captured: HashMap<String, CapturedLogin>,
+}
+
+impl MitmSimplifiedServer {
+    pub fn new(parameters: SrpParameters) -> Self {
+        Self {
+            parameters,
+            captured: HashMap::new(),
+        }
+    }
+
+    /// tries all `candidates` in parallel and returns the password used by
+    /// `email`, if it was found
+    pub fn crack_password(&self, email: &str, candidates: &[String]) -> Option<String> {
+        let login = self.captured.get(email)?;
+        let mac = login.mac.as_ref()?;
+        let n = self.parameters.n();
+        let g = self.parameters.g();
+
+        candidates
+            .par_iter()
+            .find_any(|password| {
+                let x = hash_to_int(&[], password.as_bytes());
+                let s = &login.a_pub * g.modpow(&x, n) % n;
+                let k = session_key(&s);
+                hmac_sha256(&k[..], &[]) == *mac
+            })
+            .cloned()
+    }
+}
+
+impl SimplifiedSrpServer for MitmSimplifiedServer {
+    fn start_handshake(
+        &mut self,
+        email: &str,
+        a_pub: &BigUint,
+    ) -> Result<(Bytes, BigUint, BigUint), SrpError> {
+        self.captured.insert(
+            email.to_owned(),
+            CapturedLogin {
+                a_pub: a_pub.clone(),
+                mac: None,
+            },
+        );
+
+        let b_pub = self.parameters.g().clone();
+        Ok((Bytes::from(vec![]), b_pub, BigUint::one()))
+    }
+
+    fn verify(&mut self, email: &str, mac: &Bytes) -> Result<bool, SrpError> {
+        let login = self
+            .captured
+            .get_mut(email)
+            .ok_or_else(|| SrpError::NoPendingHandshake(email.to_owned()))?;
+        login.mac = Some(mac.clone());
+
+        // we cannot know if the password was correct, so let's pretend it was
+        Ok(true)
+    }
+}
+
+/// the client side of the simplified SRP protocol
+pub struct SimplifiedSrpClient {
+    parameters: SrpParameters,
+    email: String,
+    password: String,
+}
+
+impl SimplifiedSrpClient {
+    pub fn new(parameters: SrpParameters, email: &str, password: &str) -> Self {
+        Self {
+            parameters,
+            email: email.to_owned(),
+            password: password.to_owned(),
+        }
+    }
+
+    pub fn login(&self, server: &mut impl SimplifiedSrpServer) -> Result<bool, SrpError> {
+        let n = self.parameters.n();
+        let a = rand::thread_rng().gen_biguint_below(n);
+        let a_pub = self.parameters.g().modpow(&a, n);
+
+        let (salt, b_pub, u) = server.start_handshake(&self.email, &a_pub)?;
+
+        // S = B**(a + ux) % n
+        let x = hash_to_int(&salt[..], self.password.as_bytes());
+        let s = b_pub.modpow(&(a + u * x), n);
+        let k = session_key(&s);
+
+        server.verify(&self.email, &hmac_sha256(&k[..], &salt[..]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{
+        HonestSimplifiedServer, MitmSimplifiedServer, SimplifiedSrpClient, SrpParameters,
+    };
+
+    const EMAIL: &str = "alice@example.com";
+
+    fn wordlist() -> Vec<String> {
+        fs::read_to_string("data/wordlist.txt")
+            .unwrap()
+            .lines()
+            .take(200)
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_honest_login() {
+        let mut server = HonestSimplifiedServer::new(SrpParameters::default());
+        server.register(EMAIL, "secret");
+
+        let client = SimplifiedSrpClient::new(SrpParameters::default(), EMAIL, "secret");
+        assert!(client.login(&mut server).unwrap());
+
+        let client = SimplifiedSrpClient::new(SrpParameters::default(), EMAIL, "guess");
+        assert!(!client.login(&mut server).unwrap());
+    }
+
+    #[test]
+    fn test_offline_dictionary_attack() {
+        let wordlist = wordlist();
+        let password = &wordlist[wordlist.len() / 2];
+
+        let mut server = MitmSimplifiedServer::new(SrpParameters::default());
+        let client = SimplifiedSrpClient::new(SrpParameters::default(), EMAIL, password);
+        assert!(client.login(&mut server).unwrap());
+
+        assert_eq!(server.crack_password(EMAIL, &wordlist).as_ref(), Some(password));
+        assert_eq!(server.crack_password("bob@example.com", &wordlist), None);
+    }
+}
@@ -0,0 +1,83 @@
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use crate::{hmac_sha256, SrpError, SrpServer};
+
+use super::session_key;
+
+/// a malicious SRP client which logs in without knowing the password
+/// (challenge 37)
+///
+/// If the client sends `A = i * N`, the server calculates
+/// `S = (A * v**u) ** b % N = 0`, so the client knows the session key
+/// without knowing anything about the password.
+pub struct ZeroKeyClient {
+    email: String,
+    multiple: u32,
+}
+
+impl ZeroKeyClient {
+    /// creates a client which sends `A = multiple * N`
+    pub fn new(email: &str, multiple: u32) -> Self {
+        Self {
+            email: email.to_owned(),
+            multiple,
+        }
+    }
+
+    pub fn login(&self, server: &mut SrpServer) -> Result<bool, SrpError> {
+        let a_pub = server.parameters().n() * BigUint::from(self.multiple);
+        let (salt, _) = server.start_handshake(&self.email, &a_pub)?;
+
+        let k = session_key(&BigUint::zero());
+        server.verify(&self.email, &hmac_sha256(&k[..], &salt[..]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SrpClient, SrpError, SrpParameters, SrpServer, ZeroKeyClient};
+
+    const EMAIL: &str = "alice@example.com";
+    const PASSWORD: &str = "correct horse battery staple";
+
+    #[test]
+    fn test_honest_login() {
+        let mut server = SrpServer::new(SrpParameters::default());
+        server.register(EMAIL, PASSWORD);
+
+        let client = SrpClient::new(SrpParameters::default(), EMAIL, PASSWORD);
+        assert!(client.login(&mut server).unwrap());
+
+        let client = SrpClient::new(SrpParameters::default(), EMAIL, "wrong password");
+        assert!(!client.login(&mut server).unwrap());
+    }
+
+    #[test]
+    fn test_zero_key_bypass() {
+        let mut server = SrpServer::new(SrpParameters::default());
+        server.register(EMAIL, PASSWORD);
+
+        for multiple in 0..4 {
+            assert!(ZeroKeyClient::new(EMAIL, multiple)
+                .login(&mut server)
+                .unwrap());
+        }
+    }
+
+    #[test]
+    fn test_hardened_server() {
+        let mut server = SrpServer::hardened(SrpParameters::default());
+        server.register(EMAIL, PASSWORD);
+
+        for multiple in 0..4 {
+            assert!(matches!(
+                ZeroKeyClient::new(EMAIL, multiple).login(&mut server),
+                Err(SrpError::IllegalPublicKey)
+            ));
+        }
+
+        let client = SrpClient::new(SrpParameters::default(), EMAIL, PASSWORD);
+        assert!(client.login(&mut server).unwrap());
+    }
+}